@@ -1,8 +1,8 @@
-///! Usage:
-///!
-///! ```
-///! $ cargo run --example main -- <HOST> <API_USER> <API_PASSWORD>
-///! ```
+//! Usage:
+//!
+//! ```
+//! $ cargo run --example main -- <HOST> <API_USER> <API_PASSWORD>
+//! ```
 use std::{env, error::Error};
 use std::{net::IpAddr, str::FromStr};
 
@@ -1,8 +1,8 @@
-///! Usage:
-///!
-///! ```
-///! $ cargo run --example wlan-passphrase-generator -- <HOST> <API_USER> <API_PASSWORD> <SSID>
-///! ```
+//! Usage:
+//!
+//! ```
+//! $ cargo run --example wlan-passphrase-generator -- <HOST> <API_USER> <API_PASSWORD> <SSID>
+//! ```
 use std::{env, error::Error};
 
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
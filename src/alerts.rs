@@ -1,15 +1,21 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime};
 use reqwest::Method;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{http::ApiV1, Unified, UnifiedError};
 
-#[derive(Debug)]
+/// Alert raised by the controller about the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
+  /// Date and time the alert was raised
   pub time: NaiveDateTime,
+  /// Subsystem the alert is about
   pub subsystem: String,
+  /// Name of the device the alert is about, if any
   pub device: Option<String>,
+  /// Description of the alert
   pub message: String,
+  /// Has the alert been archived?
   pub archived: bool,
 }
 
@@ -41,7 +47,7 @@ impl Unified {
         let device = alert.sw_name.or(alert.ap_name).or(alert.gw_name);
 
         Alert {
-          time: NaiveDateTime::from_timestamp(alert.time / 1000, 0),
+          time: DateTime::from_timestamp(alert.time / 1000, 0).map(|time| time.naive_utc()).unwrap_or_default(),
           device,
           subsystem: alert.subsystem.to_uppercase(),
           message: alert.message,
@@ -0,0 +1,137 @@
+use std::{
+  any::Any,
+  collections::HashMap,
+  sync::{Arc, Mutex, PoisonError},
+  time::{Duration, Instant},
+};
+
+use reqwest::Method;
+
+use crate::{http::UnifiData, Unified, UnifiedError};
+
+/// Collections of objects that can be cached.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Collection {
+  Sites,
+  Clients,
+  Devices,
+  Networks,
+  WirelessNetworks,
+  ApGroups,
+  RadiusProfiles,
+  RadiusUsers,
+}
+
+type CacheKey = (Option<String>, Collection);
+type CacheEntry = (Instant, Arc<dyn Any + Send + Sync>);
+
+/// Responses kept around for a configurable amount of time.
+///
+/// Clones of a handle share the same cached entries.
+#[derive(Clone, Default)]
+pub(crate) struct Cache {
+  ttls: HashMap<Collection, Duration>,
+  entries: Arc<Mutex<HashMap<CacheKey, CacheEntry>>>,
+}
+
+impl Cache {
+  fn key(site: Option<&str>, collection: Collection) -> CacheKey {
+    match collection {
+      Collection::Sites => (None, collection),
+      _ => (site.map(ToString::to_string), collection),
+    }
+  }
+
+  fn get<T>(&self, site: Option<&str>, collection: Collection) -> Option<T>
+  where
+    T: Clone + 'static,
+  {
+    let ttl = self.ttls.get(&collection)?;
+    let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+
+    match entries.get(&Cache::key(site, collection)) {
+      Some((stored_at, value)) if stored_at.elapsed() < *ttl => value.downcast_ref::<T>().cloned(),
+      _ => None,
+    }
+  }
+
+  fn insert<T>(&self, site: Option<&str>, collection: Collection, value: T)
+  where
+    T: Send + Sync + 'static,
+  {
+    if self.ttls.contains_key(&collection) {
+      let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+
+      entries.insert(Cache::key(site, collection), (Instant::now(), Arc::new(value)));
+    }
+  }
+
+  fn remove(&self, site: Option<&str>, collection: Collection) {
+    self.entries.lock().unwrap_or_else(PoisonError::into_inner).remove(&Cache::key(site, collection));
+  }
+}
+
+impl Unified {
+  /// Cache the objects of a collection for the given amount of time.
+  ///
+  /// Listing the objects of that collection returns the cached objects until
+  /// they expire. Objects created, updated or deleted through this crate
+  /// invalidate the cache for their collection.
+  ///
+  /// # Arguments
+  ///
+  ///  * `collection` - Collection of objects to cache
+  ///  * `ttl`        - How long the objects are kept
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp")
+  ///   .cache(Collection::Devices, Duration::from_secs(30))
+  ///   .cache(Collection::Networks, Duration::from_secs(300));
+  /// ```
+  pub fn cache(self, collection: Collection, ttl: Duration) -> Unified {
+    self.configure(|inner| {
+      inner.cache.ttls.insert(collection, ttl);
+    })
+  }
+
+  /// Discard the cached objects of a collection on a site.
+  ///
+  /// # Arguments
+  ///
+  ///  * `site`       - Name of the site to use (ignored for [`Collection::Sites`])
+  ///  * `collection` - Collection of objects to discard
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// unifi.invalidate("default", Collection::Clients);
+  /// ```
+  pub fn invalidate(&self, site: &str, collection: Collection) {
+    self.inner.cache.remove(Some(site), collection);
+  }
+
+  /// Discard all cached objects.
+  pub fn clear_cache(&self) {
+    self.inner.cache.entries.lock().unwrap_or_else(PoisonError::into_inner).clear();
+  }
+
+  /// Query a collection, going through the cache if it is enabled for it.
+  pub(crate) async fn cached<T, O>(&self, site: Option<&str>, collection: Collection, path: &str) -> Result<O, UnifiedError>
+  where
+    T: UnifiData<Output = O>,
+    O: Clone + Send + Sync + 'static,
+  {
+    if let Some(objects) = self.inner.cache.get::<O>(site, collection) {
+      return Ok(objects);
+    }
+
+    let objects = self.request::<T>(Method::GET, path).query().await?;
+
+    self.inner.cache.insert(site, collection, objects.clone());
+
+    Ok(objects)
+  }
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Change made to a single attribute of an object.
+///
+/// Attributes are named after the key used by the controller (e.g.
+/// `x_passphrase` for the passphrase of a wireless network). An attribute that
+/// is absent on either side is represented as `Value::Null`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+  /// Name of the changed attribute
+  pub field: String,
+  /// Value of the attribute before the change
+  pub before: Value,
+  /// Value of the attribute after the change
+  pub after: Value,
+}
+
+/// Serialize the modelled attributes of an object into a JSON map.
+pub(crate) fn snapshot<T>(modelled: &T) -> Map<String, Value>
+where
+  T: Serialize,
+{
+  match serde_json::to_value(modelled) {
+    Ok(Value::Object(fields)) => fields,
+    _ => Map::new(),
+  }
+}
+
+/// List the attributes that differ between two snapshots of an object.
+pub(crate) fn diff(before: &Map<String, Value>, after: &Map<String, Value>) -> Vec<FieldChange> {
+  let mut fields = before.keys().chain(after.keys()).collect::<Vec<_>>();
+
+  fields.sort();
+  fields.dedup();
+
+  fields
+    .into_iter()
+    .filter_map(|field| {
+      let old = before.get(field).unwrap_or(&Value::Null);
+      let new = after.get(field).unwrap_or(&Value::Null);
+
+      match old == new {
+        true => None,
+        false => Some(FieldChange {
+          field: field.clone(),
+          before: old.clone(),
+          after: new.clone(),
+        }),
+      }
+    })
+    .collect()
+}
+
+/// Build a request body containing only the changed attributes.
+pub(crate) fn body(changes: &[FieldChange]) -> Value {
+  Value::Object(changes.iter().map(|change| (change.field.clone(), change.after.clone())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::{json, Map, Value};
+
+  use super::{body, diff, FieldChange};
+  use crate::{Network, RestResource, Unified};
+
+  fn object(value: Value) -> Map<String, Value> {
+    match value {
+      Value::Object(object) => object,
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn diff_lists_changed_attributes() {
+    let before = object(json!({ "name": "Employees", "vlan": "20", "enabled": true }));
+    let after = object(json!({ "name": "Employees", "vlan": "30", "domain_name": "acme.corp" }));
+
+    assert_eq!(
+      diff(&before, &after),
+      vec![
+        FieldChange {
+          field: "domain_name".to_string(),
+          before: Value::Null,
+          after: json!("acme.corp"),
+        },
+        FieldChange {
+          field: "enabled".to_string(),
+          before: json!(true),
+          after: Value::Null,
+        },
+        FieldChange {
+          field: "vlan".to_string(),
+          before: json!("20"),
+          after: json!("30"),
+        },
+      ]
+    );
+
+    assert!(diff(&before, &before).is_empty());
+  }
+
+  #[test]
+  fn body_sends_cleared_attributes_as_null() {
+    let unified = Unified::new("unifi.test");
+    let loaded = object(json!({ "_id": "n1", "name": "Employees", "purpose": "corporate", "networkgroup": "LAN1", "domain_name": "acme.corp" }));
+
+    let mut network = Network::from_json(&unified, "default", loaded).unwrap();
+    network.domain = None;
+
+    let changes = network.changes();
+
+    assert_eq!(body(&changes), json!({ "domain_name": null }));
+  }
+
+  #[test]
+  fn snapshot_leaves_id_out() {
+    let unified = Unified::new("unifi.test");
+    let loaded = object(json!({ "_id": "n1", "name": "Employees", "purpose": "corporate", "networkgroup": "LAN1" }));
+
+    let mut network = Network::from_json(&unified, "default", loaded).unwrap();
+    network.id = "n2".to_string();
+
+    assert!(!network.to_json().contains_key("_id"));
+    assert!(network.changes().is_empty());
+  }
+}
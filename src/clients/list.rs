@@ -1,10 +1,9 @@
 use std::{net::IpAddr, str::FromStr};
 
-use chrono::NaiveDateTime;
+use chrono::DateTime;
 use macaddr::MacAddr;
-use reqwest::Method;
 
-use crate::{clients::types::*, http::ApiV1, Unified, UnifiedError};
+use crate::{cache::Collection, clients::types::*, http::ApiV1, Unified, UnifiedError};
 
 impl Unified {
   /// List all known network clients on the given site.
@@ -18,36 +17,12 @@ impl Unified {
   /// ```
   /// let clients = unifi.clients("default").await?;
   /// ```
-  pub async fn clients(&self, site: &str) -> Result<Vec<Client<'_>>, UnifiedError> {
-    let response = self.request::<ApiV1<Vec<RemoteClient>>>(Method::GET, &format!("/api/s/{}/stat/sta", site)).query().await?;
+  pub async fn clients(&self, site: &str) -> Result<Vec<Client>, UnifiedError> {
+    let response = self
+      .cached::<ApiV1<Vec<RemoteClient>>, _>(Some(site), Collection::Clients, &format!("/api/s/{}/stat/sta", site))
+      .await?;
 
-    let clients = response
-      .into_iter()
-      .map(|client| {
-        let seen = client.last_seen.map(|ts| NaiveDateTime::from_timestamp(ts, 0));
-
-        Client {
-          unified: self,
-          site: site.to_string(),
-
-          id: client.id,
-          name: client.name,
-          mac: MacAddr::from_str(&client.mac).unwrap(),
-          oui: client.oui,
-          hostname: client.hostname,
-          ip: client.ip.and_then(|ip| IpAddr::from_str(&ip).ok()),
-          identity: client.identity,
-          last_seen: seen,
-          wired: client.is_wired,
-          guest: client.is_guest,
-          authorized: client.authorized,
-          rx_bytes: client.rx_bytes,
-          tx_bytes: client.tx_bytes,
-          wired_rx_bytes: client.wired_rx_bytes,
-          wired_tx_bytes: client.wired_tx_bytes,
-        }
-      })
-      .collect();
+    let clients = response.into_iter().map(|client| Client::from_remote(self, site, client)).collect();
 
     Ok(clients)
   }
@@ -58,6 +33,10 @@ impl Unified {
   /// to use is selected according to the variant of
   /// [`ClientRef`] provided to the function.
   ///
+  /// Lookups by MAC address are resolved by the controller, first among
+  /// connected clients and then among known clients. Other lookups list all
+  /// connected clients on the site.
+  ///
   /// # Arguments
   ///
   ///  * `site`       - Name of the site to use
@@ -68,21 +47,54 @@ impl Unified {
   /// ```
   /// let client = unifi.client("default", ClientRef::Ip("1.2.3.4")).await?;
   /// ```
-  pub async fn client(&self, site: &str, client_ref: ClientRef<'_>) -> Result<Option<Client<'_>>, UnifiedError> {
-    let mac = match client_ref {
-      ClientRef::Mac(mac) => Some(MacAddr::from_str(mac).map_err(|_| UnifiedError::InvalidMacAddress)?),
-      _ => None,
-    };
+  pub async fn client(&self, site: &str, client_ref: ClientRef<'_>) -> Result<Option<Client>, UnifiedError> {
+    let matches: Box<dyn Fn(&Client) -> bool + Send + Sync> = match client_ref {
+      ClientRef::Mac(mac) => {
+        let mac = MacAddr::from_str(mac).map_err(|_| UnifiedError::InvalidMacAddress)?.to_string().to_lowercase();
+
+        let client = match self.lookup::<RemoteClient>(&format!("/api/s/{}/stat/sta/{}", site, mac)).await? {
+          Some(client) => Some(client),
+          None => self.lookup::<RemoteClient>(&format!("/api/s/{}/stat/user/{}", site, mac)).await?,
+        };
+
+        return Ok(client.map(|client| Client::from_remote(self, site, client)));
+      }
 
-    let ip = match client_ref {
-      ClientRef::Ip(ip) => Some(IpAddr::from_str(ip).map_err(|_| UnifiedError::InvalidIpAddress)?),
-      _ => None,
+      ClientRef::Id(id) => Box::new(move |client: &Client| client.id == id),
+      ClientRef::Ip(ip) => {
+        let ip = IpAddr::from_str(ip).map_err(|_| UnifiedError::InvalidIpAddress)?;
+        Box::new(move |client: &Client| client.ip == Some(ip))
+      }
     };
 
-    Ok(self.clients(&site).await?.into_iter().find(|client| match client_ref {
-      ClientRef::Id(id) => client.id == id,
-      ClientRef::Mac(_) => mac.map(|mac| client.mac == mac).unwrap_or_default(),
-      ClientRef::Ip(_) => client.ip == ip,
-    }))
+    Ok(self.clients(site).await?.into_iter().find(|client| matches(client)))
+  }
+}
+
+impl Client {
+  /// Build a client from the object returned by the controller.
+  pub(super) fn from_remote(unified: &Unified, site: &str, client: RemoteClient) -> Client {
+    let seen = client.last_seen.and_then(|ts| DateTime::from_timestamp(ts, 0)).map(|time| time.naive_utc());
+
+    Client {
+      unified: Some(unified.clone()),
+      site: site.to_string(),
+
+      id: client.id,
+      name: client.name,
+      mac: MacAddr::from_str(&client.mac).unwrap(),
+      oui: client.oui,
+      hostname: client.hostname,
+      ip: client.ip.and_then(|ip| IpAddr::from_str(&ip).ok()),
+      identity: client.identity,
+      last_seen: seen,
+      wired: client.is_wired,
+      guest: client.is_guest,
+      authorized: client.authorized,
+      rx_bytes: client.rx_bytes,
+      tx_bytes: client.tx_bytes,
+      wired_rx_bytes: client.wired_rx_bytes,
+      wired_tx_bytes: client.wired_tx_bytes,
+    }
   }
 }
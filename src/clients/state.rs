@@ -1,9 +1,9 @@
 use reqwest::Method;
 use serde_json::json;
 
-use crate::{clients::types::Client, http::ApiV1NoData, UnifiedError};
+use crate::{cache::Collection, clients::types::Client, http::ApiV1NoData, UnifiedError};
 
-impl<'c> Client<'c> {
+impl Client {
   /// Block the client from accessing the networks.
   ///
   /// # Example
@@ -40,26 +40,31 @@ impl<'c> Client<'c> {
   /// }
   /// ```
   pub async fn kick(&self) -> Result<(), UnifiedError> {
-    self
-      .unified
+    let unified = self.handle()?;
+
+    unified
       .request::<ApiV1NoData>(Method::POST, &format!("/api/s/{}/cmd/stamgr", self.site))
       .map(|r| r.json(&json!({ "cmd": "kick-sta", "mac": self.mac.to_string() })))
       .query()
       .await?;
 
+    unified.invalidate(&self.site, Collection::Clients);
+
     Ok(())
   }
 
   async fn set_blocked(&self, blocked: bool) -> Result<(), UnifiedError> {
     let command = if blocked { "block-sta" } else { "unblock-sta" };
+    let unified = self.handle()?;
 
-    self
-      .unified
+    unified
       .request::<ApiV1NoData>(Method::POST, &format!("/api/s/{}/cmd/stamgr", self.site))
       .map(|r| r.json(&json!({ "cmd": command, "mac": self.mac.to_string() })))
       .query()
       .await?;
 
+    unified.invalidate(&self.site, Collection::Clients);
+
     Ok(())
   }
 }
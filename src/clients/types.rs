@@ -2,23 +2,26 @@ use std::net::IpAddr;
 
 use chrono::NaiveDateTime;
 use macaddr::MacAddr;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Unified;
+use crate::{Unified, UnifiedError};
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub(super) struct RemoteClient {
   #[serde(rename = "_id")]
   pub id: String,
   pub name: Option<String>,
   pub mac: String,
+  #[serde(default)]
   pub oui: String,
   pub hostname: Option<String>,
   pub ip: Option<String>,
   #[serde(rename = "1x_identity")]
   pub identity: Option<String>,
   pub last_seen: Option<i64>,
+  #[serde(default)]
   pub is_wired: bool,
+  #[serde(default)]
   pub is_guest: bool,
   #[serde(default)]
   pub authorized: bool,
@@ -43,11 +46,13 @@ pub enum ClientRef<'r> {
 }
 
 /// A client connected to the network.
-#[derive(Derivative)]
+#[derive(Clone, Derivative)]
 #[derivative(Debug)]
-pub struct Client<'c> {
+#[derive(Serialize, Deserialize)]
+pub struct Client {
   #[derivative(Debug = "ignore")]
-  pub(crate) unified: &'c Unified,
+  #[serde(skip)]
+  pub(crate) unified: Option<Unified>,
   pub(crate) site: String,
 
   /// Internal ID
@@ -55,6 +60,7 @@ pub struct Client<'c> {
   /// Name (alias) of the client
   pub name: Option<String>,
   /// MAC address
+  #[serde(with = "crate::util::mac_address")]
   pub mac: MacAddr,
   /// Vendor name
   pub oui: String,
@@ -81,3 +87,15 @@ pub struct Client<'c> {
   /// Number of bytes sent by the client over a wired connection
   pub wired_tx_bytes: u64,
 }
+
+impl Client {
+  /// Bind a deserialized client to a handle (see [serialization](crate#serialization)).
+  pub fn attach(&mut self, unified: &Unified) {
+    self.unified = Some(unified.clone());
+  }
+
+  /// Handle the client is bound to.
+  pub(crate) fn handle(&self) -> Result<&Unified, UnifiedError> {
+    self.unified.as_ref().ok_or(UnifiedError::NotAttached)
+  }
+}
@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Network, RadiusUser, ResourceKind, RestResource, Unified, UnifiedError, WirelessNetwork};
+
+/// What to do when an object with the same name exists on the target site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionPolicy {
+  /// Leave the existing object untouched
+  Skip,
+  /// Replace the attributes of the existing object
+  Overwrite,
+  /// Create the object under a new name (e.g. "Guests (2)")
+  Rename,
+}
+
+/// Objects to copy, selected by name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CopySelection {
+  /// Names of the wired networks to copy
+  #[serde(default)]
+  pub networks: Vec<String>,
+  /// Names of the wireless networks to copy
+  #[serde(default)]
+  pub wireless_networks: Vec<String>,
+  /// Names of the RADIUS users to copy
+  #[serde(default)]
+  pub radius_users: Vec<String>,
+}
+
+/// Outcome of the copy of a single object.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyAction {
+  Created,
+  Overwritten,
+  Renamed,
+  Skipped,
+}
+
+/// Object copied to the target site.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopiedObject {
+  /// Kind of object
+  pub resource: ResourceKind,
+  /// Name of the object on the source site
+  pub name: String,
+  /// Name of the object on the target site
+  pub target_name: String,
+  /// What was done on the target site
+  pub action: CopyAction,
+}
+
+/// Report of a copy between two sites.
+///
+/// An object that could not be copied does not prevent the others from being
+/// copied, and its error is collected in `errors`.
+#[derive(Debug)]
+pub struct CopyReport {
+  /// Objects copied, skipped or overwritten on the target site
+  pub copied: Vec<CopiedObject>,
+  /// Objects that could not be copied, with the reason
+  pub errors: Vec<(ResourceKind, String, UnifiedError)>,
+}
+
+impl CopyReport {
+  /// Were all the selected objects handled?
+  pub fn is_complete(&self) -> bool {
+    self.errors.is_empty()
+  }
+}
+
+/// Object that can be copied to another site.
+trait Copyable: RestResource + Clone {
+  const KIND: ResourceKind;
+
+  fn name(&self) -> &str;
+  fn set_name(&mut self, name: &str);
+
+  /// Bind the object to the target site, either as a new object or as a
+  /// replacement for an existing one.
+  fn retarget(&mut self, target: &Unified, site: &str, existing: Option<&Self>);
+}
+
+impl Copyable for Network {
+  const KIND: ResourceKind = ResourceKind::Network;
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn set_name(&mut self, name: &str) {
+    self.name = name.to_string();
+  }
+
+  fn retarget(&mut self, target: &Unified, site: &str, existing: Option<&Network>) {
+    self.unified = Some(target.clone());
+    self.site = site.to_string();
+    self.id = existing.map(|network| network.id.clone()).unwrap_or_default();
+    self.raw = existing.map(|network| network.raw.clone()).unwrap_or_default();
+    self.snapshot = existing.map(|network| network.snapshot.clone()).unwrap_or_default();
+  }
+}
+
+impl Copyable for WirelessNetwork {
+  const KIND: ResourceKind = ResourceKind::WirelessNetwork;
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn set_name(&mut self, name: &str) {
+    self.name = name.to_string();
+  }
+
+  fn retarget(&mut self, target: &Unified, site: &str, existing: Option<&WirelessNetwork>) {
+    self.unified = Some(target.clone());
+    self.site = site.to_string();
+    self.id = existing.map(|wlan| wlan.id.clone()).unwrap_or_default();
+    self.raw = existing.map(|wlan| wlan.raw.clone()).unwrap_or_default();
+    self.snapshot = existing.map(|wlan| wlan.snapshot.clone()).unwrap_or_default();
+  }
+}
+
+impl Copyable for RadiusUser {
+  const KIND: ResourceKind = ResourceKind::RadiusUser;
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn set_name(&mut self, name: &str) {
+    self.name = name.to_string();
+  }
+
+  fn retarget(&mut self, target: &Unified, site: &str, existing: Option<&RadiusUser>) {
+    self.unified = Some(target.clone());
+    self.site = site.to_string();
+    self.id = existing.map(|user| user.id.clone()).unwrap_or_default();
+    self.raw = existing.map(|user| user.raw.clone()).unwrap_or_default();
+    self.snapshot = existing.map(|user| user.snapshot.clone()).unwrap_or_default();
+  }
+}
+
+impl Unified {
+  /// Copy objects from a site to another one, possibly on another
+  /// controller.
+  ///
+  /// Networks are copied first, then wireless networks, then RADIUS users.
+  /// References held by wireless networks (network, AP groups and RADIUS
+  /// profile) are remapped by name to their equivalent on the target site,
+  /// which must exist there or be copied along. Only the attributes modelled
+  /// by this crate are copied.
+  ///
+  /// # Arguments
+  ///
+  ///  * `site`        - Name of the site to copy from
+  ///  * `target`      - Handle to the controller to copy to
+  ///  * `target_site` - Name of the site to copy to
+  ///  * `selection`   - Names of the objects to copy
+  ///  * `policy`      - What to do with objects already existing on the target site
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let selection = CopySelection {
+  ///   networks: vec!["Employees".to_string()],
+  ///   wireless_networks: vec!["ACME Corp".to_string()],
+  ///   ..Default::default()
+  /// };
+  ///
+  /// let report = unifi.copy_to("default", &unifi, "branch", &selection, CollisionPolicy::Skip).await?;
+  /// ```
+  pub async fn copy_to(&self, site: &str, target: &Unified, target_site: &str, selection: &CopySelection, policy: CollisionPolicy) -> Result<CopyReport, UnifiedError> {
+    let mut report = CopyReport { copied: vec![], errors: vec![] };
+
+    let source_networks = self.networks(site).await?;
+    let target_networks = target.networks(target_site).await?;
+    let target_wlans = target.wireless_networks(target_site).await?;
+    let target_users = target.radius_users(target_site).await?;
+
+    let mut networks = References {
+      names: source_networks.iter().map(|network| (network.id.clone(), network.name.clone())).collect(),
+      targets: target_networks.iter().map(|network| (network.name.clone(), network.id.clone())).collect(),
+    };
+    let ap_groups = References {
+      names: self.ap_groups(site).await?.into_iter().map(|group| (group.id, group.name)).collect(),
+      targets: target.ap_groups(target_site).await?.into_iter().map(|group| (group.name, group.id)).collect(),
+    };
+    let profiles = References {
+      names: self.radius_profiles(site).await?.into_iter().map(|profile| (profile.id, profile.name)).collect(),
+      targets: target.radius_profiles(target_site).await?.into_iter().map(|profile| (profile.name, profile.id)).collect(),
+    };
+
+    for name in &selection.networks {
+      match source_networks.iter().find(|network| &network.name == name) {
+        Some(network) => match copy_object(target, target_site, network.clone(), &target_networks, policy).await {
+          Ok((copied, id)) => {
+            networks.targets.insert(name.clone(), id);
+            report.copied.push(copied);
+          }
+          Err(err) => report.errors.push((ResourceKind::Network, name.clone(), err)),
+        },
+        None => report.errors.push((ResourceKind::Network, name.clone(), not_found(ResourceKind::Network, name))),
+      }
+    }
+
+    if !selection.wireless_networks.is_empty() {
+      let source_wlans = self.wireless_networks(site).await?;
+
+      for name in &selection.wireless_networks {
+        let outcome = match source_wlans.iter().find(|wlan| &wlan.name == name) {
+          Some(wlan) => match remap_wireless_network(wlan, &networks, &ap_groups, &profiles) {
+            Ok(wlan) => copy_object(target, target_site, wlan, &target_wlans, policy).await.map(|(copied, _)| copied),
+            Err(err) => Err(err),
+          },
+          None => Err(not_found(ResourceKind::WirelessNetwork, name)),
+        };
+
+        match outcome {
+          Ok(copied) => report.copied.push(copied),
+          Err(err) => report.errors.push((ResourceKind::WirelessNetwork, name.clone(), err)),
+        }
+      }
+    }
+
+    if !selection.radius_users.is_empty() {
+      let source_users = self.radius_users(site).await?;
+
+      for name in &selection.radius_users {
+        let outcome = match source_users.iter().find(|user| &user.name == name) {
+          Some(user) => copy_object(target, target_site, user.clone(), &target_users, policy).await.map(|(copied, _)| copied),
+          None => Err(not_found(ResourceKind::RadiusUser, name)),
+        };
+
+        match outcome {
+          Ok(copied) => report.copied.push(copied),
+          Err(err) => report.errors.push((ResourceKind::RadiusUser, name.clone(), err)),
+        }
+      }
+    }
+
+    Ok(report)
+  }
+}
+
+/// Copy a single object to the target site, returning its ID there.
+async fn copy_object<R>(target: &Unified, site: &str, mut object: R, existing: &[R], policy: CollisionPolicy) -> Result<(CopiedObject, String), UnifiedError>
+where
+  R: Copyable,
+{
+  let name = object.name().to_string();
+
+  let (action, id) = match (existing.iter().find(|other| other.name() == name), policy) {
+    (Some(other), CollisionPolicy::Skip) => (CopyAction::Skipped, other.id().to_string()),
+
+    (Some(other), CollisionPolicy::Overwrite) => {
+      object.retarget(target, site, Some(other));
+      target.update_resource(&object).await?;
+
+      (CopyAction::Overwritten, other.id().to_string())
+    }
+
+    (collision, _) => {
+      if collision.is_some() {
+        let taken = existing.iter().map(Copyable::name).collect::<Vec<_>>();
+        let renamed = (2..).map(|n| format!("{} ({})", name, n)).find(|candidate| !taken.contains(&candidate.as_str())).unwrap_or_default();
+
+        object.set_name(&renamed);
+      }
+
+      object.retarget(target, site, None);
+      let created = target.create_resource(&object).await?;
+
+      match collision {
+        Some(_) => (CopyAction::Renamed, created.id().to_string()),
+        None => (CopyAction::Created, created.id().to_string()),
+      }
+    }
+  };
+
+  let copied = CopiedObject {
+    resource: R::KIND,
+    target_name: object.name().to_string(),
+    name,
+    action,
+  };
+
+  Ok((copied, id))
+}
+
+/// Names of the objects referenced on the source site, and IDs of their
+/// equivalent on the target site.
+struct References {
+  names: HashMap<String, String>,
+  targets: HashMap<String, String>,
+}
+
+impl References {
+  /// Find the ID of the object with the same name on the target site.
+  fn remap(&self, kind: &str, id: &str) -> Result<String, UnifiedError> {
+    let name = self.names.get(id).ok_or_else(|| UnifiedError::NoSuchObject(format!("{} {}", kind, id)))?;
+
+    self
+      .targets
+      .get(name)
+      .cloned()
+      .ok_or_else(|| UnifiedError::NoSuchObject(format!("{} \"{}\" on target site", kind, name)))
+  }
+}
+
+/// Point the references of a wireless network to objects on the target site.
+fn remap_wireless_network(wlan: &WirelessNetwork, networks: &References, ap_groups: &References, profiles: &References) -> Result<WirelessNetwork, UnifiedError> {
+  let mut wlan = wlan.clone();
+
+  wlan.network = wlan.network.as_deref().map(|id| networks.remap("network", id)).transpose()?;
+  wlan.ap_groups = wlan.ap_groups.iter().map(|id| ap_groups.remap("AP group", id)).collect::<Result<_, _>>()?;
+  wlan.radius_profile = wlan.radius_profile.as_deref().map(|id| profiles.remap("RADIUS profile", id)).transpose()?;
+
+  Ok(wlan)
+}
+
+fn not_found(kind: ResourceKind, name: &str) -> UnifiedError {
+  UnifiedError::NoSuchObject(format!("{} \"{}\"", kind, name))
+}
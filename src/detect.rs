@@ -0,0 +1,89 @@
+use std::sync::PoisonError;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::{unified::Flavour, Unified, UnifiedError};
+
+#[derive(Deserialize)]
+struct RemoteStatus {
+  meta: RemoteStatusMeta,
+}
+
+#[derive(Deserialize)]
+struct RemoteStatusMeta {
+  server_version: Option<String>,
+}
+
+impl Unified {
+  /// Probe the controller to find out how to talk to it.
+  ///
+  /// UniFi OS consoles (UDM, UDR, Cloud Key Gen2+, etc.) serve their own
+  /// landing page on `/`, whereas standalone installs of the Network
+  /// Application redirect to `/manage`. The detected type of controller
+  /// selects the login endpoint and the API prefix used for all subsequent
+  /// requests. The version of the Network Application is also recorded, when
+  /// available.
+  ///
+  /// This is performed automatically by [`Unified::auth`], or on the first
+  /// request when authenticating otherwise, unless [`Unified::udm_pro`] was
+  /// used.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").detect().await?;
+  ///
+  /// println!("{:?}", unifi.version());
+  /// ```
+  pub async fn detect(self) -> Result<Unified, UnifiedError> {
+    self.probe().await?;
+
+    Ok(self)
+  }
+
+  /// Was the controller detected as running on UniFi OS?
+  pub fn is_udm_pro(&self) -> bool {
+    self.flavour().is_udm_pro
+  }
+
+  /// Version of the Network Application, if it was detected.
+  pub fn version(&self) -> Option<String> {
+    self.flavour().version
+  }
+
+  pub(crate) fn detected(&self) -> bool {
+    self.flavour().detected
+  }
+
+  fn flavour(&self) -> Flavour {
+    self.inner.flavour.read().unwrap_or_else(PoisonError::into_inner).clone()
+  }
+
+  /// Detect the type of controller, for this handle and all its clones.
+  pub(crate) async fn probe(&self) -> Result<(), UnifiedError> {
+    let response = self.http()?.get(format!("{}://{}/", self.inner.scheme.as_str(), self.inner.host)).send().await?;
+    let is_udm_pro = response.status() == StatusCode::OK && response.url().path() == "/";
+
+    let flavour = Flavour {
+      is_udm_pro,
+      detected: true,
+      version: self.probe_version(is_udm_pro).await,
+    };
+
+    *self.inner.flavour.write().unwrap_or_else(PoisonError::into_inner) = flavour;
+
+    Ok(())
+  }
+
+  async fn probe_version(&self, is_udm_pro: bool) -> Option<String> {
+    let url = match is_udm_pro {
+      true => format!("{}://{}/proxy/network/status", self.inner.scheme.as_str(), self.inner.host),
+      false => format!("{}://{}/status", self.inner.scheme.as_str(), self.inner.host),
+    };
+
+    let response = self.http().ok()?.get(&url).send().await.ok()?;
+
+    response.json::<RemoteStatus>().await.ok()?.meta.server_version
+  }
+}
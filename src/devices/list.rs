@@ -2,9 +2,8 @@ use std::{net::IpAddr, str::FromStr, time::Duration};
 
 use macaddr::MacAddr;
 use num_traits::FromPrimitive;
-use reqwest::Method;
 
-use crate::{devices::types::*, http::ApiV1, Unified, UnifiedError};
+use crate::{cache::Collection, devices::types::*, http::ApiV1, Unified, UnifiedError};
 
 impl Unified {
   /// List all adopted device on the given site.
@@ -19,37 +18,11 @@ impl Unified {
   /// let devices = unifi.devices("default").await?;
   /// ```
   pub async fn devices(&self, site: &str) -> Result<Vec<Device>, UnifiedError> {
-    let response = self.request::<ApiV1<Vec<RemoteDevice>>>(Method::GET, &format!("/api/s/{}/stat/device", site)).query().await?;
+    let response = self
+      .cached::<ApiV1<Vec<RemoteDevice>>, _>(Some(site), Collection::Devices, &format!("/api/s/{}/stat/device", site))
+      .await?;
 
-    let devices = response
-      .into_iter()
-      .map(|device| {
-        let ip = if !device.network_table.is_empty() {
-          device
-            .network_table
-            .into_iter()
-            .find_map(|network| if network.persistent { IpAddr::from_str(&network.ip).ok() } else { None })
-        } else {
-          IpAddr::from_str(&device.ip).ok()
-        };
-
-        let state = FromPrimitive::from_u32(device.state).unwrap_or(DeviceState::Unknown);
-
-        Device {
-          id: device.id,
-          name: device.name,
-          model: device.model,
-          mac: MacAddr::from_str(&device.mac).unwrap(),
-          ip,
-          version: device.version,
-          upgradable: device.upgradable,
-          state,
-          uptime: Duration::from_secs(device.uptime),
-          rx_bytes: device.rx_bytes,
-          tx_bytes: device.tx_bytes,
-        }
-      })
-      .collect();
+    let devices = response.into_iter().map(Device::from).collect();
 
     Ok(devices)
   }
@@ -60,6 +33,9 @@ impl Unified {
   /// to use is selected according to the variant of [`DeviceRef`] provided to
   /// the function.
   ///
+  /// Lookups by MAC address are resolved by the controller. Other lookups list
+  /// all devices on the site.
+  ///
   /// # Arguments
   ///
   ///  * `site`       - Name of the site to use
@@ -71,20 +47,50 @@ impl Unified {
   /// let device = unifi.device("default", DeviceRef::Ip("1.2.3.4")).await?;
   /// ```
   pub async fn device(&self, site: &str, device_ref: DeviceRef<'_>) -> Result<Option<Device>, UnifiedError> {
-    let mac = match device_ref {
-      DeviceRef::Mac(mac) => Some(MacAddr::from_str(mac).map_err(|_| UnifiedError::InvalidMacAddress)?),
-      _ => None,
+    let matches: Box<dyn Fn(&Device) -> bool + Send + Sync> = match device_ref {
+      DeviceRef::Mac(mac) => {
+        let mac = MacAddr::from_str(mac).map_err(|_| UnifiedError::InvalidMacAddress)?.to_string().to_lowercase();
+        let device = self.lookup::<RemoteDevice>(&format!("/api/s/{}/stat/device/{}", site, mac)).await?;
+
+        return Ok(device.map(Device::from));
+      }
+
+      DeviceRef::Id(id) => Box::new(move |device: &Device| device.id == id),
+      DeviceRef::Ip(ip) => {
+        let ip = IpAddr::from_str(ip).map_err(|_| UnifiedError::InvalidIpAddress)?;
+        Box::new(move |device: &Device| device.ip == Some(ip))
+      }
     };
 
-    let ip = match device_ref {
-      DeviceRef::Ip(ip) => Some(IpAddr::from_str(ip).map_err(|_| UnifiedError::InvalidIpAddress)?),
-      _ => None,
+    Ok(self.devices(site).await?.into_iter().find(|device| matches(device)))
+  }
+}
+
+impl From<RemoteDevice> for Device {
+  fn from(device: RemoteDevice) -> Device {
+    let ip = if !device.network_table.is_empty() {
+      device
+        .network_table
+        .into_iter()
+        .find_map(|network| if network.persistent { IpAddr::from_str(&network.ip).ok() } else { None })
+    } else {
+      IpAddr::from_str(&device.ip).ok()
     };
 
-    Ok(self.devices(site).await?.into_iter().find(|device| match device_ref {
-      DeviceRef::Id(id) => device.id == id,
-      DeviceRef::Mac(_) => mac.map(|mac| device.mac == mac).unwrap_or_default(),
-      DeviceRef::Ip(_) => device.ip == ip,
-    }))
+    let state = FromPrimitive::from_u32(device.state).unwrap_or(DeviceState::Unknown);
+
+    Device {
+      id: device.id,
+      name: device.name,
+      model: device.model,
+      mac: MacAddr::from_str(&device.mac).unwrap(),
+      ip,
+      version: device.version,
+      upgradable: device.upgradable,
+      state,
+      uptime: Duration::from_secs(device.uptime),
+      rx_bytes: device.rx_bytes,
+      tx_bytes: device.tx_bytes,
+    }
   }
 }
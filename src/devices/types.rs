@@ -1,10 +1,10 @@
-use std::{net::IpAddr, time::Duration};
+use std::{fmt, net::IpAddr, time::Duration};
 
 use macaddr::MacAddr;
 use num_derive::FromPrimitive;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub(super) struct RemoteDevice {
   #[serde(rename = "_id")]
   pub id: String,
@@ -22,7 +22,7 @@ pub(super) struct RemoteDevice {
   pub tx_bytes: u64,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub(super) struct RemoteDeviceNetwork {
   #[serde(rename = "attr_no_delete", default)]
   pub persistent: bool,
@@ -41,7 +41,8 @@ pub enum DeviceRef<'r> {
 
 /// States a device can be in.
 #[allow(missing_docs)]
-#[derive(Debug, FromPrimitive)]
+#[derive(Debug, Clone, Copy, FromPrimitive, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DeviceState {
   Disconnected = 0,
   Connected = 1,
@@ -56,36 +57,39 @@ pub enum DeviceState {
   AdoptionRequired = 10,
   AdoptionFailed = 11,
   Isolated = 12,
+  #[serde(rename = "rf_scanning")]
   RFScanning = 13,
   ManagedByOther = 14,
   Unknown = 15,
 }
 
-impl ToString for DeviceState {
-  fn to_string(&self) -> String {
-    match self {
-      Self::Disconnected => "Disconnected".to_string(),
-      Self::Connected => "Connected".to_string(),
-      Self::PendingAdoption => "Pending Adoption".to_string(),
-      Self::PendingUpgrade => "Pending Upgrade".to_string(),
-      Self::Upgrading => "Upgrading".to_string(),
-      Self::Provisionning => "Provisionning".to_string(),
-      Self::HeartbeatMissed => "Heartbeat Missed".to_string(),
-      Self::Adopting => "Adopting".to_string(),
-      Self::Deleting => "Deleting".to_string(),
-      Self::InformError => "Inform Error".to_string(),
-      Self::AdoptionRequired => "Adoption Required".to_string(),
-      Self::AdoptionFailed => "Adoption Failed".to_string(),
-      Self::Isolated => "Isolated".to_string(),
-      Self::RFScanning => "RF Scanning".to_string(),
-      Self::ManagedByOther => "Managed By Other".to_string(),
-      Self::Unknown => "Unknown".to_string(),
-    }
+impl fmt::Display for DeviceState {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let value = match self {
+      Self::Disconnected => "Disconnected",
+      Self::Connected => "Connected",
+      Self::PendingAdoption => "Pending Adoption",
+      Self::PendingUpgrade => "Pending Upgrade",
+      Self::Upgrading => "Upgrading",
+      Self::Provisionning => "Provisionning",
+      Self::HeartbeatMissed => "Heartbeat Missed",
+      Self::Adopting => "Adopting",
+      Self::Deleting => "Deleting",
+      Self::InformError => "Inform Error",
+      Self::AdoptionRequired => "Adoption Required",
+      Self::AdoptionFailed => "Adoption Failed",
+      Self::Isolated => "Isolated",
+      Self::RFScanning => "RF Scanning",
+      Self::ManagedByOther => "Managed By Other",
+      Self::Unknown => "Unknown",
+    };
+
+    write!(f, "{}", value)
   }
 }
 
 /// A Unifi device adopted on the controller.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Device {
   /// Internal ID
   pub id: String,
@@ -94,6 +98,7 @@ pub struct Device {
   /// Hardware model
   pub model: String,
   /// MAC address
+  #[serde(with = "crate::util::mac_address")]
   pub mac: MacAddr,
   /// IP address
   pub ip: Option<IpAddr>,
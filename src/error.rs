@@ -1,19 +1,51 @@
+use std::fmt;
+
 use reqwest::StatusCode;
+use serde_json::Value;
 use thiserror::Error;
 
+use crate::http::tls;
+
 /// Error type that can be returned by unified.
 #[allow(missing_docs)]
 #[derive(Debug, Error)]
 pub enum UnifiedError {
   #[error("network error")]
-  NetworkError(#[from] reqwest::Error),
-  #[error("http error: {0}")]
-  HttpError(StatusCode),
+  NetworkError(#[source] reqwest::Error),
+  #[error("certificate mismatch: expected fingerprint {expected}, got {actual}")]
+  CertificateMismatch { expected: String, actual: String },
+  #[error("http error: {status} on {path}")]
+  HttpError { status: StatusCode, path: String },
+  #[error("authentication required: {0}")]
+  AuthRequired(ApiError),
+  #[error("invalid object: {0}")]
+  InvalidObject(ApiError),
+  #[error("duplicate name: {0}")]
+  DuplicateName(ApiError),
+  #[error("permission denied: {0}")]
+  NoPermission(ApiError),
+  #[error("no such site: {0}")]
+  NoSuchSite(ApiError),
   #[error("unifi error: {0}")]
-  UnifiError(String),
+  UnifiError(ApiError),
+  #[error("multi-factor authentication required")]
+  MfaRequired,
+  #[error("object was modified on the controller: {path}")]
+  Conflict { path: String, loaded: Value, current: Value },
+  #[error("object was not loaded from the controller, conflicts cannot be checked: {path}")]
+  NotLoaded { path: String },
+  #[error("object is not attached to a handle")]
+  NotAttached,
+
+  #[error("could not parse object: {0}")]
+  InvalidResponse(#[source] serde_json::Error),
 
   #[error("missing attribute: {0}")]
   MissingAttribute(String),
+  #[error("no such object: {0}")]
+  NoSuchObject(String),
+  #[error("unsupported snapshot version: {0}")]
+  UnsupportedVersion(u32),
 
   #[error("unknown error")]
   Unknown,
@@ -22,4 +54,114 @@ pub enum UnifiedError {
   InvalidMacAddress,
   #[error("could not parse IP address")]
   InvalidIpAddress,
+  #[error("could not parse certificate fingerprint")]
+  InvalidFingerprint,
+}
+
+impl From<reqwest::Error> for UnifiedError {
+  fn from(err: reqwest::Error) -> UnifiedError {
+    match tls::find_mismatch(&err) {
+      Some(mismatch) => UnifiedError::CertificateMismatch {
+        expected: mismatch.expected.clone(),
+        actual: mismatch.actual.clone(),
+      },
+
+      None => UnifiedError::NetworkError(err),
+    }
+  }
+}
+
+/// Error reported by the controller in response to a request.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+  /// Error key returned by the controller (e.g. `api.err.Invalid`)
+  pub key: String,
+  /// HTTP status of the response
+  pub status: StatusCode,
+  /// Path of the request that failed
+  pub path: String,
+}
+
+impl fmt::Display for ApiError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.path.is_empty() {
+      true => write!(f, "{}", self.key),
+      false => write!(f, "{} (HTTP {} on {})", self.key, self.status.as_u16(), self.path),
+    }
+  }
+}
+
+impl UnifiedError {
+  /// Build the error matching an error key returned by the controller.
+  pub(crate) fn from_key(key: &str) -> UnifiedError {
+    let error = ApiError {
+      key: key.to_string(),
+      status: StatusCode::OK,
+      path: String::new(),
+    };
+
+    match key {
+      "api.err.LoginRequired" => UnifiedError::AuthRequired(error),
+      "api.err.Invalid" | "api.err.InvalidObject" | "api.err.IdInvalid" | "api.err.InvalidPayload" => UnifiedError::InvalidObject(error),
+      "api.err.NoPermission" => UnifiedError::NoPermission(error),
+      "api.err.NoSiteContext" => UnifiedError::NoSuchSite(error),
+      key if key.starts_with("api.err.Duplicate") || key.ends_with("AlreadyExists") => UnifiedError::DuplicateName(error),
+      _ => UnifiedError::UnifiError(error),
+    }
+  }
+
+  /// Attach the status and path of the failed request to an error reported
+  /// by the controller.
+  pub(crate) fn in_request(mut self, status: StatusCode, path: &str) -> UnifiedError {
+    if let Some(error) = self.api_error_mut() {
+      error.status = status;
+      error.path = path.to_string();
+    }
+
+    self
+  }
+
+  /// Get the details of the error reported by the controller, if applicable.
+  pub fn api_error(&self) -> Option<&ApiError> {
+    match self {
+      UnifiedError::AuthRequired(error)
+      | UnifiedError::InvalidObject(error)
+      | UnifiedError::DuplicateName(error)
+      | UnifiedError::NoPermission(error)
+      | UnifiedError::NoSuchSite(error)
+      | UnifiedError::UnifiError(error) => Some(error),
+      _ => None,
+    }
+  }
+
+  fn api_error_mut(&mut self) -> Option<&mut ApiError> {
+    match self {
+      UnifiedError::AuthRequired(error)
+      | UnifiedError::InvalidObject(error)
+      | UnifiedError::DuplicateName(error)
+      | UnifiedError::NoPermission(error)
+      | UnifiedError::NoSuchSite(error)
+      | UnifiedError::UnifiError(error) => Some(error),
+      _ => None,
+    }
+  }
+
+  /// Whether the error means the looked up object does not exist.
+  pub(crate) fn is_not_found(&self) -> bool {
+    match self {
+      UnifiedError::HttpError { status, .. } => *status == StatusCode::NOT_FOUND,
+      UnifiedError::InvalidObject(error) => error.key == "api.err.IdInvalid",
+      UnifiedError::UnifiError(error) => error.key.starts_with("api.err.Unknown") || error.key == "api.err.NotFound",
+      _ => false,
+    }
+  }
+
+  /// Whether the error was caused by the controller rejecting the session.
+  pub(crate) fn is_session_expired(&self) -> bool {
+    match self {
+      UnifiedError::HttpError { status, .. } => *status == StatusCode::UNAUTHORIZED || *status == StatusCode::FORBIDDEN,
+      UnifiedError::AuthRequired(_) => true,
+      _ => false,
+    }
+  }
 }
@@ -1,15 +1,21 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime};
 use reqwest::Method;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{http::ApiV1, Unified, UnifiedError};
 
-#[derive(Debug)]
+/// Event that happened on the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
+  /// Date and time the event happened
   pub time: NaiveDateTime,
+  /// Subsystem the event relates to
   pub subsystem: String,
+  /// Name of the device or client involved, if any
   pub device: Option<String>,
+  /// SSID of the wireless network involved, if any
   pub ssid: Option<String>,
+  /// Description of the event
   pub message: String,
 }
 
@@ -45,7 +51,7 @@ impl Unified {
         let subsystem = event.subsystem.map(|subsystem| subsystem.to_uppercase()).unwrap_or_else(|| "AUTH".to_string());
 
         Event {
-          time: NaiveDateTime::from_timestamp(event.time / 1000, 0),
+          time: DateTime::from_timestamp(event.time / 1000, 0).map(|time| time.naive_utc()).unwrap_or_default(),
           device,
           subsystem,
           ssid: event.ssid,
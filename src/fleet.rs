@@ -0,0 +1,122 @@
+use std::future::Future;
+
+use futures_util::{stream, StreamExt};
+
+use crate::{alerts::Alert, Device, Site, SiteHandle, Unified, UnifiedError};
+
+/// Results of a query run across several sites.
+///
+/// Each result is tagged with the site it came from. A site for which the
+/// query failed does not prevent the others from being queried, and its error
+/// is collected in `errors`.
+#[derive(Debug)]
+pub struct FleetResults<T> {
+  /// Successful results, with the site they were returned by
+  pub results: Vec<(Site, T)>,
+  /// Errors, with the site that returned them
+  pub errors: Vec<(Site, UnifiedError)>,
+}
+
+impl<T> FleetResults<T> {
+  /// Did the query succeed on every site?
+  pub fn is_complete(&self) -> bool {
+    self.errors.is_empty()
+  }
+}
+
+impl<T> FleetResults<Vec<T>> {
+  /// Flatten the results into a single list of items tagged with their site.
+  pub fn flatten(self) -> Vec<(Site, T)> {
+    self.results.into_iter().flat_map(|(site, items)| items.into_iter().map(move |item| (site.clone(), item))).collect()
+  }
+}
+
+impl Unified {
+  /// Run a site-scoped query across several sites.
+  ///
+  /// At most `concurrency` sites are queried at the same time. Results are
+  /// returned in the order of the provided sites.
+  ///
+  /// # Arguments
+  ///
+  ///  * `sites`       - Sites to run the query on
+  ///  * `concurrency` - Maximum number of sites queried concurrently
+  ///  * `query`       - Query to run, given a handle to each site
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let sites = unifi.sites().await?.into_iter().filter(|site| site.alarms > 0);
+  /// let clients = unifi.fan_out(sites, 4, |site| async move { site.clients().await }).await;
+  /// ```
+  pub async fn fan_out<I, T, F, Fut>(&self, sites: I, concurrency: usize, query: F) -> FleetResults<T>
+  where
+    I: IntoIterator<Item = Site>,
+    F: Fn(SiteHandle) -> Fut,
+    Fut: Future<Output = Result<T, UnifiedError>>,
+  {
+    let outcomes = stream::iter(sites)
+      .map(|site| {
+        let outcome = query(site.handle(self));
+
+        async move { (site, outcome.await) }
+      })
+      .buffered(concurrency.max(1))
+      .collect::<Vec<_>>()
+      .await;
+
+    outcomes.into_iter().fold(
+      FleetResults {
+        results: Vec::new(),
+        errors: Vec::new(),
+      },
+      |mut fleet, (site, outcome)| {
+        match outcome {
+          Ok(result) => fleet.results.push((site, result)),
+          Err(err) => fleet.errors.push((site, err)),
+        }
+
+        fleet
+      },
+    )
+  }
+
+  /// List the devices adopted on every site of the controller.
+  ///
+  /// # Arguments
+  ///
+  ///  * `concurrency` - Maximum number of sites queried concurrently
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// for (site, device) in unifi.fleet_devices(4).await?.flatten() {
+  ///   println!("{}: {}", site.name, device.name);
+  /// }
+  /// ```
+  pub async fn fleet_devices(&self, concurrency: usize) -> Result<FleetResults<Vec<Device>>, UnifiedError> {
+    let sites = self.sites().await?;
+
+    Ok(self.fan_out(sites, concurrency, |site| async move { site.devices().await }).await)
+  }
+
+  /// List the alerts raised on every site of the controller.
+  ///
+  /// # Arguments
+  ///
+  ///  * `limit`       - Maximum number of alerts to return for each site
+  ///  * `concurrency` - Maximum number of sites queried concurrently
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// for (site, alert) in unifi.fleet_alerts(Some(10), 4).await?.flatten() {
+  ///   println!("{}: {}", site.name, alert.message);
+  /// }
+  /// ```
+  pub async fn fleet_alerts(&self, limit: Option<u64>, concurrency: usize) -> Result<FleetResults<Vec<Alert>>, UnifiedError> {
+    let sites = self.sites().await?;
+
+    Ok(self.fan_out(sites, concurrency, |site| async move { site.alerts(limit).await }).await)
+  }
+}
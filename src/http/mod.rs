@@ -1,3 +1,5 @@
+mod retry;
+pub(crate) mod tls;
 mod types;
 
 use std::marker::PhantomData;
@@ -5,53 +7,130 @@ use std::marker::PhantomData;
 use reqwest::{Method, RequestBuilder};
 use serde::Deserialize;
 
+pub use self::retry::RetryPolicy;
 pub(crate) use self::types::*;
 use crate::{Unified, UnifiedError};
 
-/// HTTP scheme to be used for the connection to the Unifi controller.
-pub(crate) struct UnifiRequest<F> {
-  pub(crate) builder: RequestBuilder,
+type RequestMapper<'r> = Box<dyn FnMut(RequestBuilder) -> RequestBuilder + Send + 'r>;
+
+/// Request to the Unifi controller, built on top of the shared HTTP client.
+pub(crate) struct UnifiRequest<'r, F> {
+  pub(crate) unified: &'r Unified,
+  pub(crate) method: Method,
+  pub(crate) path: String,
+  pub(crate) mappers: Vec<RequestMapper<'r>>,
   pub(crate) _phantom: PhantomData<F>,
 }
 
-impl<T> UnifiRequest<T>
+impl<'r, T> UnifiRequest<'r, T>
 where
   T: for<'de> Deserialize<'de>,
 {
-  pub fn map<F>(mut self, mut callback: F) -> UnifiRequest<T>
+  pub fn map<F>(mut self, callback: F) -> UnifiRequest<'r, T>
   where
-    F: FnMut(RequestBuilder) -> RequestBuilder,
+    F: FnMut(RequestBuilder) -> RequestBuilder + Send + 'r,
   {
-    self.builder = callback(self.builder);
+    self.mappers.push(Box::new(callback));
     self
   }
 
-  pub async fn query<O>(self) -> Result<O, UnifiedError>
+  pub async fn query<O>(mut self) -> Result<O, UnifiedError>
   where
     T: UnifiData<Output = O>,
   {
-    Ok(self.builder.send().await?.deserialize::<T>().await?.catch()?)
+    let mut attempt = 0;
+    let mut reauthenticated = false;
+
+    loop {
+      match self.send().await {
+        Err(err) if !reauthenticated && err.is_session_expired() && self.unified.relogin().await? => reauthenticated = true,
+
+        Err(err) if self.should_retry(&err, attempt) => {
+          if let Some(ref policy) = self.unified.inner.retry {
+            tokio::time::sleep(policy.delay(attempt)).await;
+          }
+
+          attempt += 1;
+        }
+
+        result => return result,
+      }
+    }
+  }
+
+  async fn send<O>(&mut self) -> Result<O, UnifiedError>
+  where
+    T: UnifiData<Output = O>,
+  {
+    if !self.unified.detected() {
+      self.unified.probe().await?;
+    }
+
+    let response = self.build()?.send().await?;
+    let status = response.status();
+
+    response.deserialize::<T>(&self.path).await?.catch().map_err(|err| err.in_request(status, &self.path))
+  }
+
+  fn should_retry(&self, err: &UnifiedError, attempt: u32) -> bool {
+    match self.unified.inner.retry {
+      Some(ref policy) => policy.should_retry(&self.method, err, attempt),
+      None => false,
+    }
+  }
+
+  fn build(&mut self) -> Result<RequestBuilder, UnifiedError> {
+    let unified = self.unified;
+
+    let url = match unified.is_udm_pro() {
+      true => format!("{}://{}/proxy/network{}", unified.inner.scheme.as_str(), unified.inner.host, self.path),
+      false => format!("{}://{}{}", unified.inner.scheme.as_str(), unified.inner.host, self.path),
+    };
+
+    let builder = unified.http()?.request(self.method.clone(), &url);
+
+    let builder = match unified.inner.api_key {
+      Some(ref api_key) => builder.header("x-api-key", api_key),
+
+      None => {
+        let session = unified.session_state();
+
+        match unified.is_udm_pro() {
+          true => builder.header("cookie", session.token).header("x-csrf-token", session.csrf),
+          false => builder.header("cookie", session.token),
+        }
+      }
+    };
+
+    Ok(self.mappers.iter_mut().fold(builder, |builder, mapper| mapper(builder)))
   }
 }
 
 impl Unified {
-  pub(crate) fn request<T>(&self, method: Method, path: &str) -> UnifiRequest<T>
+  /// Fetch a single object from an endpoint returning a list.
+  ///
+  /// Returns `None` if the controller reports that the object does not exist.
+  pub(crate) async fn lookup<T>(&self, path: &str) -> Result<Option<T>, UnifiedError>
   where
     T: for<'de> Deserialize<'de>,
   {
-    let client = reqwest::ClientBuilder::new().danger_accept_invalid_certs(!self.tls_verify).build().unwrap();
-
-    let url = match self.is_udm_pro {
-      true => format!("{}://{}/proxy/network{}", self.scheme.as_str(), self.host, path),
-      false => format!("{}://{}{}", self.scheme.as_str(), self.host, path),
-    };
+    match self.request::<ApiV1<Vec<T>>>(Method::GET, path).query().await {
+      Ok(objects) => Ok(objects.into_iter().next()),
+      Err(err) if err.is_not_found() => Ok(None),
+      Err(err) => Err(err),
+    }
+  }
 
+  pub(crate) fn request<T>(&self, method: Method, path: &str) -> UnifiRequest<'_, T>
+  where
+    T: for<'de> Deserialize<'de>,
+  {
     UnifiRequest {
-      builder: match self.is_udm_pro {
-        true => client.request(method, &url).header("cookie", &self.token).header("x-csrf-token", &self.csrf),
-        false => client.request(method, &url).header("cookie", &self.token),
-      },
-      _phantom: PhantomData::<T>::default(),
+      unified: self,
+      method,
+      path: path.to_string(),
+      mappers: Vec::new(),
+      _phantom: PhantomData,
     }
   }
 }
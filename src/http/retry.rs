@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::Method;
+
+use crate::UnifiedError;
+
+/// Policy used to retry requests that failed because of transient errors.
+///
+/// Requests are retried on network errors and on 5xx responses, waiting for
+/// an exponentially increasing, randomized, delay between attempts. By
+/// default, only idempotent requests (`GET` and `HEAD`) are retried.
+///
+/// # Example
+///
+/// ```
+/// let policy = RetryPolicy::new(5).backoff(Duration::from_millis(500), Duration::from_secs(30));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+  pub(crate) max_retries: u32,
+  pub(crate) initial_backoff: Duration,
+  pub(crate) max_backoff: Duration,
+  pub(crate) retry_mutations: bool,
+}
+
+impl RetryPolicy {
+  /// Create a retry policy.
+  ///
+  /// # Arguments
+  ///
+  ///  * `max_retries` - Maximum number of times a request is retried
+  pub fn new(max_retries: u32) -> RetryPolicy {
+    RetryPolicy {
+      max_retries,
+      initial_backoff: Duration::from_millis(250),
+      max_backoff: Duration::from_secs(10),
+      retry_mutations: false,
+    }
+  }
+
+  /// Set the bounds of the delay between two attempts.
+  ///
+  /// # Arguments
+  ///
+  ///  * `initial` - Base delay before the first retry
+  ///  * `max`     - Upper bound of the delay between two attempts
+  pub fn backoff(mut self, initial: Duration, max: Duration) -> RetryPolicy {
+    self.initial_backoff = initial;
+    self.max_backoff = max;
+    self
+  }
+
+  /// Also retry `POST`, `PUT` and `DELETE` requests.
+  pub fn retry_mutations(mut self) -> RetryPolicy {
+    self.retry_mutations = true;
+    self
+  }
+
+  pub(crate) fn should_retry(&self, method: &Method, err: &UnifiedError, attempt: u32) -> bool {
+    if attempt >= self.max_retries {
+      return false;
+    }
+    if !self.retry_mutations && *method != Method::GET && *method != Method::HEAD {
+      return false;
+    }
+
+    match err {
+      UnifiedError::NetworkError(err) => err.is_timeout() || err.is_connect() || err.is_request(),
+      UnifiedError::HttpError { status, .. } => status.is_server_error(),
+      _ => err.api_error().map(|error| error.status.is_server_error()).unwrap_or_default(),
+    }
+  }
+
+  pub(crate) fn delay(&self, attempt: u32) -> Duration {
+    let ceiling = self.initial_backoff.saturating_mul(2u32.saturating_pow(attempt)).min(self.max_backoff);
+
+    ceiling.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+  }
+}
@@ -0,0 +1,110 @@
+use std::{error::Error, fmt, io, sync::Arc, time::SystemTime};
+
+use rustls::{
+  client::{ServerCertVerified, ServerCertVerifier},
+  Certificate, CertificateError, ClientConfig, ServerName,
+};
+use sha2::{Digest, Sha256};
+
+use crate::UnifiedError;
+
+/// Error raised during the TLS handshake when the certificate presented by
+/// the controller does not match the pinned fingerprint.
+#[derive(Debug)]
+pub(crate) struct FingerprintMismatch {
+  pub(crate) expected: String,
+  pub(crate) actual: String,
+}
+
+impl fmt::Display for FingerprintMismatch {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "expected certificate fingerprint {}, got {}", self.expected, self.actual)
+  }
+}
+
+impl Error for FingerprintMismatch {}
+
+/// Certificate verifier accepting only the certificate matching a SHA-256
+/// fingerprint, regardless of the authority that issued it.
+struct PinnedCertificateVerifier {
+  fingerprint: [u8; 32],
+}
+
+impl ServerCertVerifier for PinnedCertificateVerifier {
+  fn verify_server_cert(
+    &self,
+    end_entity: &Certificate,
+    _intermediates: &[Certificate],
+    _server_name: &ServerName,
+    _scts: &mut dyn Iterator<Item = &[u8]>,
+    _ocsp_response: &[u8],
+    _now: SystemTime,
+  ) -> Result<ServerCertVerified, rustls::Error> {
+    let fingerprint: [u8; 32] = Sha256::digest(&end_entity.0).into();
+
+    if fingerprint != self.fingerprint {
+      let mismatch = FingerprintMismatch {
+        expected: format_fingerprint(&self.fingerprint),
+        actual: format_fingerprint(&fingerprint),
+      };
+
+      return Err(rustls::Error::InvalidCertificate(CertificateError::Other(Arc::new(mismatch))));
+    }
+
+    Ok(ServerCertVerified::assertion())
+  }
+}
+
+pub(crate) fn pinned_config(fingerprint: [u8; 32]) -> ClientConfig {
+  ClientConfig::builder()
+    .with_safe_defaults()
+    .with_custom_certificate_verifier(Arc::new(PinnedCertificateVerifier { fingerprint }))
+    .with_no_client_auth()
+}
+
+/// Parse a hex-encoded SHA-256 fingerprint, optionally separated by colons.
+pub(crate) fn parse_fingerprint(fingerprint: &str) -> Result<[u8; 32], UnifiedError> {
+  let digits = fingerprint.chars().filter(|c| *c != ':').collect::<String>();
+
+  if digits.len() != 64 || !digits.is_ascii() {
+    return Err(UnifiedError::InvalidFingerprint);
+  }
+
+  let mut bytes = [0u8; 32];
+
+  for (index, byte) in bytes.iter_mut().enumerate() {
+    *byte = u8::from_str_radix(&digits[index * 2..index * 2 + 2], 16).map_err(|_| UnifiedError::InvalidFingerprint)?;
+  }
+
+  Ok(bytes)
+}
+
+fn format_fingerprint(fingerprint: &[u8]) -> String {
+  fingerprint.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<String>>().join(":")
+}
+
+/// Look for a fingerprint mismatch in the chain of errors that led to a
+/// failed request.
+pub(crate) fn find_mismatch<'e>(err: &'e (dyn Error + 'static)) -> Option<&'e FingerprintMismatch> {
+  let mut current = Some(err);
+
+  while let Some(err) = current {
+    if let Some(mismatch) = err.downcast_ref::<FingerprintMismatch>() {
+      return Some(mismatch);
+    }
+
+    if let Some(rustls::Error::InvalidCertificate(CertificateError::Other(inner))) = err.downcast_ref::<rustls::Error>() {
+      return inner.downcast_ref::<FingerprintMismatch>();
+    }
+
+    if let Some(inner) = err.downcast_ref::<io::Error>().and_then(io::Error::get_ref) {
+      if let Some(mismatch) = find_mismatch(inner) {
+        return Some(mismatch);
+      }
+    }
+
+    current = err.source();
+  }
+
+  None
+}
@@ -1,9 +1,12 @@
 use async_trait::async_trait;
-use reqwest::{Response, StatusCode};
-use serde::Deserialize;
+use reqwest::Response;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
 
 use crate::UnifiedError;
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub(crate) enum Scheme {
   Http,
   Https,
@@ -20,25 +23,39 @@ impl Scheme {
 
 #[async_trait]
 pub(crate) trait UnifiResponse {
-  async fn deserialize<O>(self) -> Result<O, UnifiedError>
+  async fn deserialize<O>(self, path: &str) -> Result<O, UnifiedError>
   where
     O: UnifiData;
 }
 
 #[async_trait]
 impl UnifiResponse for Response {
-  async fn deserialize<O>(self) -> Result<O, UnifiedError>
+  async fn deserialize<O>(self, path: &str) -> Result<O, UnifiedError>
   where
     O: UnifiData,
   {
-    if self.status().as_u16() > 299 && self.status() != StatusCode::BAD_REQUEST {
-      return Err(UnifiedError::HttpError(self.status()));
+    let status = self.status();
+
+    if status.is_success() {
+      return Ok(self.json::<O>().await?);
     }
 
-    Ok(self.json::<O>().await?)
+    // Failed requests usually still carry the error key in their body.
+    let body = self.json::<Value>().await.unwrap_or_default();
+
+    match error_key(&body) {
+      Some(key) => Err(UnifiedError::from_key(key).in_request(status, path)),
+      None => Err(UnifiedError::HttpError { status, path: path.to_string() }),
+    }
   }
 }
 
+/// Find the error key in the body of a failed response, for any of the
+/// envelopes used by the controller.
+fn error_key(body: &Value) -> Option<&str> {
+  body.pointer("/meta/msg").or_else(|| body.pointer("/errors/0")).or_else(|| body.get("code")).and_then(Value::as_str)
+}
+
 #[async_trait]
 pub(crate) trait UnifiData: for<'de> Deserialize<'de> {
   type Output;
@@ -75,7 +92,7 @@ where
   fn catch(self) -> Result<Self::Output, UnifiedError> {
     if self.meta.code != "ok" {
       return match self.meta.message {
-        Some(message) => Err(UnifiedError::UnifiError(message)),
+        Some(message) => Err(UnifiedError::from_key(&message)),
         None => Err(UnifiedError::Unknown),
       };
     }
@@ -91,7 +108,7 @@ impl UnifiData for ApiV1NoData {
   fn catch(self) -> Result<(), UnifiedError> {
     if self.meta.code != "ok" {
       return match self.meta.message {
-        Some(message) => Err(UnifiedError::UnifiError(message)),
+        Some(message) => Err(UnifiedError::from_key(&message)),
         None => Err(UnifiedError::Unknown),
       };
     }
@@ -128,9 +145,9 @@ impl UnifiData for UdmProAuthResponse {
   type Output = ();
 
   fn catch(self) -> Result<Self::Output, UnifiedError> {
-    if self.errors.is_some() {
-      return match self.errors.unwrap().get(0) {
-        Some(message) => Err(UnifiedError::UnifiError(message.to_string())),
+    if let Some(errors) = self.errors {
+      return match errors.first() {
+        Some(message) => Err(UnifiedError::from_key(message)),
         None => Err(UnifiedError::Unknown),
       };
     }
@@ -138,3 +155,26 @@ impl UnifiData for UdmProAuthResponse {
     Ok(())
   }
 }
+
+/// Object read from the controller, along with the raw JSON it was
+/// deserialized from.
+#[derive(Clone)]
+pub(crate) struct Raw<T> {
+  pub(crate) value: T,
+  pub(crate) raw: Map<String, Value>,
+}
+
+impl<'de, T> Deserialize<'de> for Raw<T>
+where
+  for<'a> T: Deserialize<'a>,
+{
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let raw = Map::<String, Value>::deserialize(deserializer)?;
+    let value = T::deserialize(Value::Object(raw.clone())).map_err(D::Error::custom)?;
+
+    Ok(Raw { value, raw })
+  }
+}
@@ -1,27 +1,55 @@
 #![deny(missing_docs)]
 
 //! # unified
+//!
+//! ## Serialization
+//!
+//! Objects read from the controller implement `Serialize` and `Deserialize`.
+//! A deserialized object is not bound to any controller: it must be attached
+//! to a handle with its `attach()` method before any operation is performed
+//! on it, which otherwise fails with [`UnifiedError::NotAttached`].
 
 #[macro_use]
 extern crate derivative;
 
 mod alerts;
+mod cache;
+mod changes;
 mod clients;
+mod copy;
+mod detect;
 mod devices;
 mod error;
 mod events;
+mod fleet;
 mod http;
+mod mfa;
 mod networks;
 mod radius;
+mod raw;
+mod reconcile;
+mod resource;
+mod session;
 mod sites;
+mod snapshot;
 mod unified;
 mod util;
 mod wireless;
 
+pub use reqwest::Method;
+
 pub use crate::{
+  alerts::Alert,
+  cache::Collection,
+  changes::FieldChange,
   clients::types::{Client, ClientRef},
+  copy::{CollisionPolicy, CopiedObject, CopyAction, CopyReport, CopySelection},
   devices::types::{Device, DeviceRef, DeviceState},
-  error::UnifiedError,
+  error::{ApiError, UnifiedError},
+  events::Event,
+  fleet::FleetResults,
+  http::RetryPolicy,
+  mfa::{AuthFlow, MfaChallenge},
   networks::{
     builder::NetworkBuilder,
     types::{Network, NetworkGroup, NetworkPurpose, NetworkRef},
@@ -33,7 +61,15 @@ pub use crate::{
       types::{RadiusUser, RadiusUserRef, TunnelMediumType, TunnelType},
     },
   },
-  sites::types::{Site, SiteHealth, SiteRef},
+  raw::ApiVersion,
+  reconcile::{ChangeKind, DesiredDhcp, DesiredNetwork, DesiredRadiusUser, DesiredState, DesiredWirelessNetwork, Plan, PlannedChange, ResourceKind},
+  resource::RestResource,
+  session::{Session, SessionGuard},
+  sites::{
+    handle::SiteHandle,
+    types::{Site, SiteHealth, SiteRef},
+  },
+  snapshot::{SiteSnapshot, SnapshotChange, SNAPSHOT_VERSION},
   unified::Unified,
   wireless::{
     groups::types::ApGroup,
@@ -0,0 +1,78 @@
+use crate::{Unified, UnifiedError};
+
+/// Outcome of the first step of an authentication.
+pub enum AuthFlow {
+  /// The handle is authenticated and ready to use
+  Authenticated(Unified),
+  /// The account requires a second authentication factor
+  MfaRequired(MfaChallenge),
+}
+
+/// Pending authentication waiting for a second factor.
+///
+/// Should be obtained through [`Unified::start_auth`].
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct MfaChallenge {
+  #[derivative(Debug = "ignore")]
+  unified: Unified,
+  username: String,
+  #[derivative(Debug = "ignore")]
+  password: String,
+}
+
+impl MfaChallenge {
+  /// Resubmit the credentials along with the second factor.
+  ///
+  /// # Arguments
+  ///
+  ///  * `token` - Second factor (e.g. TOTP code) for the account
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = challenge.submit("123456").await?;
+  /// ```
+  pub async fn submit(self, token: &str) -> Result<Unified, UnifiedError> {
+    self.unified.authenticate(&self.username, &self.password, Some(token)).await
+  }
+}
+
+impl Unified {
+  /// Start authenticating into a Unifi controller, allowing for a second
+  /// authentication factor to be provided afterwards.
+  ///
+  /// Works like [`Unified::auth`], except a request for a second factor is
+  /// returned as an [`MfaChallenge`] instead of an error.
+  ///
+  /// # Arguments
+  ///
+  ///  * `username` - Username of the account
+  ///  * `password` - Password of the account
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = match Unified::new("unifi.acme.corp").start_auth("joe.shmoe", "mypassword").await? {
+  ///   AuthFlow::Authenticated(unifi) => unifi,
+  ///   AuthFlow::MfaRequired(challenge) => challenge.submit(&prompt_for_token()).await?,
+  /// };
+  /// ```
+  pub async fn start_auth(self, username: &str, password: &str) -> Result<AuthFlow, UnifiedError> {
+    if !self.detected() {
+      self.probe().await?;
+    }
+
+    match self.login(username, password, None).await {
+      Ok(()) => Ok(AuthFlow::Authenticated(self.remember_credentials(username, password))),
+
+      Err(UnifiedError::MfaRequired) => Ok(AuthFlow::MfaRequired(MfaChallenge {
+        unified: self,
+        username: username.to_string(),
+        password: password.to_string(),
+      })),
+
+      Err(err) => Err(err),
+    }
+  }
+}
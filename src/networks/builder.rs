@@ -12,51 +12,51 @@ use crate::{
 };
 
 /// Builder used to configure a network.
-pub struct NetworkBuilder<'n> {
-  pub(crate) network: Network<'n>,
+pub struct NetworkBuilder {
+  pub(crate) network: Network,
 }
 
-impl<'n> NetworkBuilder<'n> {
+impl NetworkBuilder {
   /// Set the purpose (type) for the network.
-  pub fn purpose(mut self, purpose: NetworkPurpose) -> NetworkBuilder<'n> {
+  pub fn purpose(mut self, purpose: NetworkPurpose) -> NetworkBuilder {
     self.network.purpose = purpose;
     self
   }
 
   /// Set the network group (physical interface) for the network.
-  pub fn group(mut self, group: NetworkGroup) -> NetworkBuilder<'n> {
+  pub fn group(mut self, group: NetworkGroup) -> NetworkBuilder {
     self.network.group = group;
     self
   }
 
   /// Set the gateway-subnet for the network.
-  pub fn subnet(mut self, subnet: &str) -> Result<NetworkBuilder<'n>, IpNetParseError> {
+  pub fn subnet(mut self, subnet: &str) -> Result<NetworkBuilder, IpNetParseError> {
     self.network.subnet = Some(IpNet::from_str(subnet)?);
 
     Ok(self)
   }
 
   /// Set the domain name for the network.
-  pub fn domain(mut self, domain: &str) -> NetworkBuilder<'n> {
+  pub fn domain(mut self, domain: &str) -> NetworkBuilder {
     self.network.domain = Some(domain.to_string());
     self
   }
 
   /// Enable VLAN tagging and set the VLAN ID for the network.
-  pub fn vlan(mut self, vlan: u16) -> NetworkBuilder<'n> {
+  pub fn vlan(mut self, vlan: u16) -> NetworkBuilder {
     self.network.vlan_enabled = true;
     self.network.vlan = Some(vlan);
     self
   }
 
   /// Enable and configure DHCP on the network.
-  pub fn dhcp(mut self, range: (&str, &str), lease_time: Option<Duration>) -> Result<NetworkBuilder<'n>, AddrParseError> {
+  pub fn dhcp(mut self, range: (&str, &str), lease_time: Option<Duration>) -> Result<NetworkBuilder, AddrParseError> {
     let (start, end) = range;
 
     self.network.dhcp = Some(NetworkDhcp {
       enabled: true,
       start: Some(IpAddr::from_str(start)?),
-      end: Some(IpAddr::from_str(&end)?),
+      end: Some(IpAddr::from_str(end)?),
       lease_duration: lease_time,
     });
 
@@ -64,7 +64,7 @@ impl<'n> NetworkBuilder<'n> {
   }
 
   /// Disable DHCP on the network.
-  pub fn disable_dhcp(mut self) -> NetworkBuilder<'n> {
+  pub fn disable_dhcp(mut self) -> NetworkBuilder {
     if let Some(dhcp) = &mut self.network.dhcp {
       dhcp.enabled = false;
     }
@@ -73,7 +73,7 @@ impl<'n> NetworkBuilder<'n> {
   }
 
   /// Enable and configure VPN access on the network.
-  pub fn vpn(mut self, vpn_type: VpnType, preshared_key: &str) -> NetworkBuilder<'n> {
+  pub fn vpn(mut self, vpn_type: VpnType, preshared_key: &str) -> NetworkBuilder {
     self.network.vpn = Some(NetworkVpn {
       kind: vpn_type,
       preshared_key: Some(preshared_key.to_string()),
@@ -83,7 +83,7 @@ impl<'n> NetworkBuilder<'n> {
   }
 
   /// Build the network.
-  pub fn build(self) -> Result<Network<'n>, UnifiedError> {
+  pub fn build(self) -> Result<Network, UnifiedError> {
     if self.network.subnet.is_none() {
       return Err(UnifiedError::MissingAttribute("subnet".to_string()));
     }
@@ -1,12 +1,11 @@
-use reqwest::Method;
+use serde_json::Map;
 
 use crate::{
-  http::ApiV1NoData,
   networks::{builder::NetworkBuilder, types::*},
   Unified, UnifiedError,
 };
 
-impl<'n> Network<'n> {
+impl Network {
   /// Create a builder for a network.
   ///
   /// # Arguments
@@ -22,10 +21,10 @@ impl<'n> Network<'n> {
   /// let network = Network::builder(&unifi, "default", "ACME - Employees", NetworkPurpose::Corporate, NetworkGroup::Lan("LAN1".to_string()))
   ///   .build();
   /// ```
-  pub fn builder(unified: &'n Unified, site: &str, name: &str) -> NetworkBuilder<'n> {
+  pub fn builder(unified: &Unified, site: &str, name: &str) -> NetworkBuilder {
     NetworkBuilder {
       network: Network {
-        unified,
+        unified: Some(unified.clone()),
         site: site.to_string(),
 
         id: String::new(),
@@ -43,35 +42,35 @@ impl<'n> Network<'n> {
 
         dhcp: None,
         vpn: None,
+
+        raw: Map::new(),
+        snapshot: Map::new(),
       },
     }
   }
 
   /// Create a network.
   ///
+  /// The network is returned as created by the controller, with its ID and
+  /// server-side defaults populated.
+  ///
   /// # Example
   ///
   /// ```
-  /// Network::builder(&unifi, "default", "Employees", NetworkPurpose::Corporate, NetworkGroup::Lan("LAN1".to_string()))
+  /// let network = Network::builder(&unifi, "default", "Employees", NetworkPurpose::Corporate, NetworkGroup::Lan("LAN1".to_string()))
   ///   .build()
   ///   .create()
   ///   .await?;
   /// ```
-  pub async fn create(self) -> Result<(), UnifiedError> {
-    let body: RemoteNetwork = self.clone().into();
-
-    self
-      .unified
-      .request::<ApiV1NoData>(Method::POST, &format!("/api/s/{}/rest/networkconf", self.site))
-      .map(|r| r.json(&body))
-      .query()
-      .await?;
-
-    Ok(())
+  pub async fn create(self) -> Result<Network, UnifiedError> {
+    self.handle()?.create_resource(&self).await
   }
 
   /// Update the network.
   ///
+  /// Only the attributes listed by [`changes()`](Self::changes) are sent, as
+  /// described in [`Unified::update_resource`].
+  ///
   /// # Example
   ///
   /// ```
@@ -81,16 +80,7 @@ impl<'n> Network<'n> {
   /// }
   /// ```
   pub async fn update(self) -> Result<(), UnifiedError> {
-    let body: RemoteNetwork = self.clone().into();
-
-    self
-      .unified
-      .request::<ApiV1NoData>(Method::PUT, &format!("/api/s/{}/rest/networkconf/{}", self.site, self.id))
-      .map(|r| r.json(&body))
-      .query()
-      .await?;
-
-    Ok(())
+    self.handle()?.update_resource(&self).await
   }
 
   /// Delete the network.
@@ -103,12 +93,6 @@ impl<'n> Network<'n> {
   /// }
   /// ```
   pub async fn delete(self) -> Result<(), UnifiedError> {
-    self
-      .unified
-      .request::<ApiV1NoData>(Method::DELETE, &format!("/api/s/{}/rest/networkconf/{}", self.site, self.id))
-      .query()
-      .await?;
-
-    Ok(())
+    self.handle()?.delete_resource::<Network>(&self.site, &self.id).await
   }
 }
@@ -1,11 +1,17 @@
-use std::{net::IpAddr, time::Duration};
+use std::{fmt, net::IpAddr, time::Duration};
 
 use ipnet::IpNet;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-use crate::Unified;
+use crate::{
+  cache::Collection,
+  changes::{self, FieldChange},
+  http::Raw,
+  RestResource, Unified, UnifiedError,
+};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(super) struct RemoteNetwork {
   #[serde(rename = "_id", skip_serializing)]
   pub id: String,
@@ -44,7 +50,7 @@ pub(super) struct RemoteNetwork {
   pub preshared_key: Option<String>,
 }
 
-impl From<Network<'_>> for RemoteNetwork {
+impl From<Network> for RemoteNetwork {
   fn from(network: Network) -> RemoteNetwork {
     let (lan_group, wan_group) = match network.group {
       NetworkGroup::Invalid => (None, None),
@@ -91,20 +97,28 @@ pub enum NetworkRef<'r> {
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum NetworkPurpose {
+  #[serde(rename = "invalid")]
   Invalid,
+  #[serde(rename = "corporate")]
   Corporate,
+  #[serde(rename = "guest")]
   Guest,
+  #[serde(rename = "wan")]
   Wan,
+  #[serde(rename = "vlan-only")]
   VlanOnly,
+  #[serde(rename = "remote-user-vpn")]
   RemoteUserVpn,
+  #[serde(rename = "site-vpn")]
   SiteToSiteVpn,
+  #[serde(rename = "vpn-client")]
   VpnClient,
 }
 
-impl ToString for NetworkPurpose {
-  fn to_string(&self) -> String {
+impl fmt::Display for NetworkPurpose {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let value = match self {
       Self::Invalid => "invalid",
       Self::Corporate => "corporate",
@@ -116,7 +130,7 @@ impl ToString for NetworkPurpose {
       Self::VpnClient => "vpn-client",
     };
 
-    value.to_string()
+    write!(f, "{}", value)
   }
 }
 
@@ -139,7 +153,8 @@ where
 }
 
 /// Physical interface on which the network will operate
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum NetworkGroup {
   /// No network group, this should not be used
   Invalid,
@@ -149,20 +164,22 @@ pub enum NetworkGroup {
   Wan(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum VpnType {
+  #[serde(rename = "pptp-server")]
   Pptp,
+  #[serde(rename = "l2tp-server")]
   L2tp,
 }
 
-impl ToString for VpnType {
-  fn to_string(&self) -> String {
+impl fmt::Display for VpnType {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let value = match self {
       Self::Pptp => "pptp-server",
       Self::L2tp => "l2tp-server",
     };
 
-    value.to_string()
+    write!(f, "{}", value)
   }
 }
 
@@ -182,9 +199,11 @@ where
 /// A wired network configured on your controller.
 #[derive(Clone, Derivative)]
 #[derivative(Debug)]
-pub struct Network<'n> {
+#[derive(Serialize, Deserialize)]
+pub struct Network {
   #[derivative(Debug = "ignore")]
-  pub(crate) unified: &'n Unified,
+  #[serde(skip)]
+  pub(crate) unified: Option<Unified>,
   pub(crate) site: String,
 
   /// Network internal ID
@@ -214,9 +233,44 @@ pub struct Network<'n> {
   pub dhcp: Option<NetworkDhcp>,
   /// Configure a VPN on this network
   pub vpn: Option<NetworkVpn>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip)]
+  pub(crate) raw: Map<String, Value>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip)]
+  pub(crate) snapshot: Map<String, Value>,
 }
 
-#[derive(Debug, Clone)]
+impl Network {
+  /// Bind a deserialized network to a handle (see [serialization](crate#serialization)).
+  pub fn attach(&mut self, unified: &Unified) {
+    self.unified = Some(unified.clone());
+  }
+
+  /// Handle the network is bound to.
+  pub(crate) fn handle(&self) -> Result<&Unified, UnifiedError> {
+    self.unified.as_ref().ok_or(UnifiedError::NotAttached)
+  }
+
+  /// Raw JSON object the network was read from.
+  ///
+  /// This includes the attributes that are not modelled by this crate, which
+  /// are left untouched when the network is updated.
+  pub fn raw(&self) -> &Map<String, Value> {
+    &self.raw
+  }
+
+  /// Attributes changed since the network was read from the controller.
+  ///
+  /// Those are the only attributes sent to the controller by
+  /// [`update()`](Network::update).
+  pub fn changes(&self) -> Vec<FieldChange> {
+    changes::diff(&self.snapshot, &self.to_json())
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkDhcp {
   pub enabled: bool,
   pub start: Option<IpAddr>,
@@ -224,8 +278,35 @@ pub struct NetworkDhcp {
   pub lease_duration: Option<Duration>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkVpn {
   pub kind: VpnType,
   pub preshared_key: Option<String>,
 }
+
+impl RestResource for Network {
+  const COLLECTION: &'static str = "networkconf";
+  const CACHE: Option<Collection> = Some(Collection::Networks);
+
+  fn from_json(unified: &Unified, site: &str, object: Map<String, Value>) -> Result<Network, UnifiedError> {
+    let remote = Raw::deserialize(Value::Object(object)).map_err(UnifiedError::InvalidResponse)?;
+
+    Ok(Network::from_remote(unified, site, remote))
+  }
+
+  fn to_json(&self) -> Map<String, Value> {
+    changes::snapshot(&RemoteNetwork::from(self.clone()))
+  }
+
+  fn site(&self) -> &str {
+    &self.site
+  }
+
+  fn id(&self) -> &str {
+    &self.id
+  }
+
+  fn loaded(&self) -> Option<&Map<String, Value>> {
+    Some(&self.snapshot)
+  }
+}
@@ -1,9 +1,8 @@
-use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
-use crate::{http::ApiV1, radius::profiles::types::RadiusProfile, Unified, UnifiedError};
+use crate::{cache::Collection, http::ApiV1, radius::profiles::types::RadiusProfile, Unified, UnifiedError};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RemoteRadiusProfile {
   #[serde(rename = "_id")]
   pub id: String,
@@ -24,8 +23,7 @@ impl Unified {
   /// ```
   pub async fn radius_profiles(&self, site: &str) -> Result<Vec<RadiusProfile>, UnifiedError> {
     let response: Vec<RemoteRadiusProfile> = self
-      .request::<ApiV1<Vec<RemoteRadiusProfile>>>(Method::GET, &format!("/api/s/{}/rest/radiusprofile", site))
-      .query()
+      .cached::<ApiV1<Vec<RemoteRadiusProfile>>, _>(Some(site), Collection::RadiusProfiles, &format!("/api/s/{}/rest/radiusprofile", site))
       .await?;
 
     let profiles = response.into_iter().map(|network| RadiusProfile { id: network.id, name: network.name }).collect();
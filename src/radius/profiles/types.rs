@@ -1,9 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 /// RADIUS profile configured on the controller.
 ///
 /// # Limitations
 ///
 /// As of now, this interface only allows listing pre-existing profiles.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RadiusProfile {
   /// Internal ID
   pub id: String,
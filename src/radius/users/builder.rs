@@ -14,17 +14,17 @@ use crate::{radius::users::types::*, UnifiedError};
 ///      .tunnel_medium_type(TunnelMediumType::Ethernet802)
 ///      .build();
 /// ```
-pub struct RadiusUserBuilder<'ru> {
-  pub(crate) user: RadiusUser<'ru>,
+pub struct RadiusUserBuilder {
+  pub(crate) user: RadiusUser,
 }
 
-impl<'ru> RadiusUserBuilder<'ru> {
+impl RadiusUserBuilder {
   /// Place the user into a specific VLAN
   ///
   /// # Arguments
   ///
   ///  * `vlan` - VLAN identifier
-  pub fn vlan(mut self, vlan: u16) -> RadiusUserBuilder<'ru> {
+  pub fn vlan(mut self, vlan: u16) -> RadiusUserBuilder {
     self.user.vlan = Some(vlan);
     self
   }
@@ -36,7 +36,7 @@ impl<'ru> RadiusUserBuilder<'ru> {
   /// # Arguments
   ///
   ///  * `tunnel_type` - Tunnel type
-  pub fn tunnel_type(mut self, tunnel_type: TunnelType) -> RadiusUserBuilder<'ru> {
+  pub fn tunnel_type(mut self, tunnel_type: TunnelType) -> RadiusUserBuilder {
     self.user.tunnel_type = Some(tunnel_type);
     self
   }
@@ -48,13 +48,13 @@ impl<'ru> RadiusUserBuilder<'ru> {
   /// # Arguments
   ///
   ///  * `tunnel_medium_type` - Tunnel medium type
-  pub fn tunnel_medium_type(mut self, tunnel_medium_type: TunnelMediumType) -> RadiusUserBuilder<'ru> {
+  pub fn tunnel_medium_type(mut self, tunnel_medium_type: TunnelMediumType) -> RadiusUserBuilder {
     self.user.tunnel_medium_type = Some(tunnel_medium_type);
     self
   }
 
   /// Finalize the builder and get a [RadiusUser] to be created.
-  pub fn build(self) -> Result<RadiusUser<'ru>, UnifiedError> {
+  pub fn build(self) -> Result<RadiusUser, UnifiedError> {
     Ok(self.user)
   }
 }
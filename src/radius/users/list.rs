@@ -1,9 +1,9 @@
 use std::str::FromStr;
 
 use num_traits::FromPrimitive;
-use reqwest::Method;
+use serde_json::Map;
 
-use crate::{http::ApiV1, radius::users::types::*, Unified, UnifiedError};
+use crate::{changes, http::Raw, radius::users::types::*, Unified, UnifiedError};
 
 impl Unified {
   /// List all configured RADIUS users.
@@ -17,25 +17,8 @@ impl Unified {
   /// ```
   /// let users = unifi.users("default").await?;
   /// ```
-  pub async fn radius_users(&self, site: &str) -> Result<Vec<RadiusUser<'_>>, UnifiedError> {
-    let response = self.request::<ApiV1<Vec<RemoteRadiusUser>>>(Method::GET, &format!("/api/s/{}/rest/account", site)).query().await?;
-
-    let users = response
-      .into_iter()
-      .map(|user| RadiusUser {
-        unified: self,
-        site: site.to_string(),
-
-        id: user.id,
-        name: user.name,
-        vlan: user.vlan.map(|vlan| u16::from_str(&vlan).ok()).flatten(),
-        password: user.password,
-        tunnel_type: user.tunnel_type.and_then(FromPrimitive::from_u16),
-        tunnel_medium_type: user.tunnel_medium_type.and_then(FromPrimitive::from_u16),
-      })
-      .collect();
-
-    Ok(users)
+  pub async fn radius_users(&self, site: &str) -> Result<Vec<RadiusUser>, UnifiedError> {
+    self.list_resources::<RadiusUser>(site).await
   }
 
   /// Find a specific RADIUS user by the provided ref.
@@ -44,6 +27,9 @@ impl Unified {
   /// selected according to the variant of [`RadiusUserRef`] provided to the
   /// function.
   ///
+  /// Lookups by ID are resolved by the controller. Other lookups list all
+  /// RADIUS users on the site.
+  ///
   /// # Arguments
   ///
   ///  * `site` - Name of the site to use
@@ -54,10 +40,33 @@ impl Unified {
   /// ```
   /// let user = unifi.user("default", ClientRef::Name("joe.shmoe@acme.corp")).await?;
   /// ```
-  pub async fn radius_user(&self, site: &str, user_ref: RadiusUserRef<'_>) -> Result<Option<RadiusUser<'_>>, UnifiedError> {
-    Ok(self.radius_users(&site).await?.into_iter().find(|user| match user_ref {
-      RadiusUserRef::Id(id) => user.id == id,
-      RadiusUserRef::Name(name) => user.name == name,
-    }))
+  pub async fn radius_user(&self, site: &str, user_ref: RadiusUserRef<'_>) -> Result<Option<RadiusUser>, UnifiedError> {
+    match user_ref {
+      RadiusUserRef::Id(id) => self.get_resource::<RadiusUser>(site, id).await,
+      RadiusUserRef::Name(name) => Ok(self.radius_users(site).await?.into_iter().find(|user| user.name == name)),
+    }
+  }
+}
+
+impl RadiusUser {
+  /// Build a RADIUS user from the object returned by the controller.
+  pub(super) fn from_remote(unified: &Unified, site: &str, Raw { value: user, raw }: Raw<RemoteRadiusUser>) -> RadiusUser {
+    let mut user = RadiusUser {
+      unified: Some(unified.clone()),
+      site: site.to_string(),
+
+      id: user.id,
+      name: user.name,
+      vlan: user.vlan.and_then(|vlan| u16::from_str(&vlan).ok()),
+      password: user.password,
+      tunnel_type: user.tunnel_type.and_then(FromPrimitive::from_u16),
+      tunnel_medium_type: user.tunnel_medium_type.and_then(FromPrimitive::from_u16),
+
+      raw,
+      snapshot: Map::new(),
+    };
+
+    user.snapshot = changes::snapshot(&RemoteRadiusUser::from(user.clone()));
+    user
   }
 }
@@ -1,12 +1,11 @@
-use reqwest::Method;
+use serde_json::Map;
 
 use crate::{
-  http::ApiV1NoData,
   radius::users::{builder::RadiusUserBuilder, types::*},
   Unified, UnifiedError,
 };
 
-impl<'ru> RadiusUser<'ru> {
+impl RadiusUser {
   /// Create a new RadiusUser builder.
   ///
   /// # Arguments
@@ -21,10 +20,10 @@ impl<'ru> RadiusUser<'ru> {
   /// ```
   /// let user = RadiusUser::builder(&unifi, "default", "joe.shmoe", "superpassword");
   /// ```
-  pub fn builder(unified: &'ru Unified, site: &str, name: &str, password: &str) -> RadiusUserBuilder<'ru> {
+  pub fn builder(unified: &Unified, site: &str, name: &str, password: &str) -> RadiusUserBuilder {
     RadiusUserBuilder {
       user: RadiusUser {
-        unified,
+        unified: Some(unified.clone()),
         site: site.to_string(),
         id: String::new(),
         name: name.to_string(),
@@ -32,32 +31,32 @@ impl<'ru> RadiusUser<'ru> {
         vlan: None,
         tunnel_type: None,
         tunnel_medium_type: None,
+
+        raw: Map::new(),
+        snapshot: Map::new(),
       },
     }
   }
 
   /// Create a new RADIUS user.
   ///
+  /// The user is returned as created by the controller, with its ID
+  /// populated.
+  ///
   /// # Example
   ///
   /// ```
-  /// RadiusUser::builder(&unifi, "default", "joe.shmoe", "hispassword").build().create().await?;
+  /// let user = RadiusUser::builder(&unifi, "default", "joe.shmoe", "hispassword").build().create().await?;
   /// ```
-  pub async fn create(self) -> Result<(), UnifiedError> {
-    let body: RemoteRadiusUser = self.clone().into();
-
-    self
-      .unified
-      .request::<ApiV1NoData>(Method::POST, &format!("/api/s/{}/rest/account", self.site))
-      .map(|r| r.json(&body))
-      .query()
-      .await?;
-
-    Ok(())
+  pub async fn create(self) -> Result<RadiusUser, UnifiedError> {
+    self.handle()?.create_resource(&self).await
   }
 
   /// Update a RADIUS user.
   ///
+  /// See [`Unified::update_resource`] for how changed attributes and
+  /// conflicts are handled.
+  ///
   /// # Example
   ///
   /// ```
@@ -67,16 +66,7 @@ impl<'ru> RadiusUser<'ru> {
   /// }
   /// ```
   pub async fn update(self) -> Result<(), UnifiedError> {
-    let body: RemoteRadiusUser = self.clone().into();
-
-    self
-      .unified
-      .request::<ApiV1NoData>(Method::PUT, &format!("/api/s/{}/rest/account/{}", self.site, self.id))
-      .map(|r| r.json(&body))
-      .query()
-      .await?;
-
-    Ok(())
+    self.handle()?.update_resource(&self).await
   }
 
   /// Delete the RADIUS user.
@@ -89,12 +79,6 @@ impl<'ru> RadiusUser<'ru> {
   /// }
   /// ```
   pub async fn delete(self) -> Result<(), UnifiedError> {
-    self
-      .unified
-      .request::<ApiV1NoData>(Method::DELETE, &format!("/api/s/{}/rest/account/{}", self.site, self.id))
-      .query()
-      .await?;
-
-    Ok(())
+    self.handle()?.delete_resource::<RadiusUser>(&self.site, &self.id).await
   }
 }
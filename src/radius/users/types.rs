@@ -1,10 +1,16 @@
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-use crate::Unified;
+use crate::{
+  cache::Collection,
+  changes::{self, FieldChange},
+  http::Raw,
+  RestResource, Unified, UnifiedError,
+};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(super) struct RemoteRadiusUser {
   #[serde(skip_serializing, rename = "_id")]
   pub(crate) id: String,
@@ -19,7 +25,7 @@ pub(super) struct RemoteRadiusUser {
   pub(crate) tunnel_medium_type: Option<u16>,
 }
 
-impl From<RadiusUser<'_>> for RemoteRadiusUser {
+impl From<RadiusUser> for RemoteRadiusUser {
   fn from(user: RadiusUser) -> RemoteRadiusUser {
     RemoteRadiusUser {
       id: user.id,
@@ -42,7 +48,8 @@ pub enum RadiusUserRef<'r> {
 
 /// List of tunnel types for RADIUS users
 #[allow(missing_docs)]
-#[derive(Debug, Copy, Clone, FromPrimitive, ToPrimitive)]
+#[derive(Debug, Copy, Clone, FromPrimitive, ToPrimitive, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TunnelType {
   Unknown = 0,
   Pptp = 1,
@@ -62,7 +69,8 @@ pub enum TunnelType {
 
 /// Lit of tunnel media types for RADIUS users
 #[allow(missing_docs)]
-#[derive(Debug, Copy, Clone, FromPrimitive, ToPrimitive)]
+#[derive(Debug, Copy, Clone, FromPrimitive, ToPrimitive, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TunnelMediumType {
   Unknown = 0,
   Ip4 = 1,
@@ -77,6 +85,7 @@ pub enum TunnelMediumType {
   X121 = 10,
   Ipx = 11,
   AppleTalk = 12,
+  #[serde(rename = "decnet_iv")]
   DecnetIV = 13,
   BanyanVines = 14,
   E164WithNsap = 15,
@@ -85,9 +94,11 @@ pub enum TunnelMediumType {
 /// A RADIUS user configured in your RADIUS profile.
 #[derive(Clone, Derivative)]
 #[derivative(Debug)]
-pub struct RadiusUser<'ru> {
+#[derive(Serialize, Deserialize)]
+pub struct RadiusUser {
   #[derivative(Debug = "ignore")]
-  pub(crate) unified: &'ru Unified,
+  #[serde(skip)]
+  pub(crate) unified: Option<Unified>,
   pub(crate) site: String,
 
   /// Internal ID
@@ -102,4 +113,66 @@ pub struct RadiusUser<'ru> {
   pub tunnel_type: Option<TunnelType>,
   /// Low-level protocol used for this tunnel medium
   pub tunnel_medium_type: Option<TunnelMediumType>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip)]
+  pub(crate) raw: Map<String, Value>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip)]
+  pub(crate) snapshot: Map<String, Value>,
+}
+
+impl RadiusUser {
+  /// Bind a deserialized RADIUS user to a handle (see [serialization](crate#serialization)).
+  pub fn attach(&mut self, unified: &Unified) {
+    self.unified = Some(unified.clone());
+  }
+
+  /// Handle the user is bound to.
+  pub(crate) fn handle(&self) -> Result<&Unified, UnifiedError> {
+    self.unified.as_ref().ok_or(UnifiedError::NotAttached)
+  }
+
+  /// Raw JSON object the RADIUS user was read from.
+  ///
+  /// This includes the attributes that are not modelled by this crate, which
+  /// are left untouched when the user is updated.
+  pub fn raw(&self) -> &Map<String, Value> {
+    &self.raw
+  }
+
+  /// Attributes changed since the RADIUS user was read from the controller.
+  ///
+  /// Those are the only attributes sent to the controller by
+  /// [`update()`](RadiusUser::update).
+  pub fn changes(&self) -> Vec<FieldChange> {
+    changes::diff(&self.snapshot, &self.to_json())
+  }
+}
+
+impl RestResource for RadiusUser {
+  const COLLECTION: &'static str = "account";
+  const CACHE: Option<Collection> = Some(Collection::RadiusUsers);
+
+  fn from_json(unified: &Unified, site: &str, object: Map<String, Value>) -> Result<RadiusUser, UnifiedError> {
+    let remote = Raw::deserialize(Value::Object(object)).map_err(UnifiedError::InvalidResponse)?;
+
+    Ok(RadiusUser::from_remote(unified, site, remote))
+  }
+
+  fn to_json(&self) -> Map<String, Value> {
+    changes::snapshot(&RemoteRadiusUser::from(self.clone()))
+  }
+
+  fn site(&self) -> &str {
+    &self.site
+  }
+
+  fn id(&self) -> &str {
+    &self.id
+  }
+
+  fn loaded(&self) -> Option<&Map<String, Value>> {
+    Some(&self.snapshot)
+  }
 }
@@ -0,0 +1,65 @@
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+  http::{ApiV1, ApiV2, UnifiData},
+  Unified, UnifiedError,
+};
+
+/// Version of the controller API, which determines the response envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+  /// Classic API (`/api/...`), wrapping responses in `meta` and `data`
+  V1,
+  /// Newer API (`/v2/api/...`), returning bare responses
+  V2,
+}
+
+impl Unified {
+  /// Send an arbitrary request to the controller.
+  ///
+  /// This is an escape hatch for endpoints not covered by this crate. The
+  /// request is authenticated like any other, and `path` is prefixed as
+  /// required on UniFi OS consoles. The response envelope is unwrapped
+  /// according to `version`, and its payload is deserialized into `T`, which
+  /// can be `serde_json::Value`.
+  ///
+  /// # Arguments
+  ///
+  ///  * `method`  - HTTP method to use
+  ///  * `path`    - Path of the endpoint (e.g. `/api/s/default/stat/health`)
+  ///  * `version` - Version of the API the endpoint belongs to
+  ///  * `body`    - Optional JSON body to send
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let health: serde_json::Value = unifi.raw(Method::GET, "/api/s/default/stat/health", ApiVersion::V1, None).await?;
+  ///
+  /// unifi
+  ///   .raw::<serde_json::Value>(Method::POST, "/api/s/default/cmd/devmgr", ApiVersion::V1, Some(json!({ "cmd": "restart", "mac": "01:23:45:67:89:ab" })))
+  ///   .await?;
+  /// ```
+  pub async fn raw<T>(&self, method: Method, path: &str, version: ApiVersion, body: Option<Value>) -> Result<T, UnifiedError>
+  where
+    T: for<'de> Deserialize<'de>,
+  {
+    match version {
+      ApiVersion::V1 => self.send_raw::<ApiV1<T>, T>(method, path, body).await,
+      ApiVersion::V2 => self.send_raw::<ApiV2<T>, T>(method, path, body).await,
+    }
+  }
+
+  async fn send_raw<E, T>(&self, method: Method, path: &str, body: Option<Value>) -> Result<T, UnifiedError>
+  where
+    E: UnifiData<Output = T>,
+  {
+    let request = self.request::<E>(method, path);
+
+    match body {
+      Some(body) => request.map(move |r| r.json(&body)).query().await,
+      None => request.query().await,
+    }
+  }
+}
@@ -0,0 +1,825 @@
+use std::{collections::HashMap, fmt, net::IpAddr, time::Duration};
+
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+  changes::FieldChange, networks::types::NetworkDhcp, Network, NetworkGroup, NetworkPurpose, RadiusUser, TunnelMediumType, TunnelType, Unified, UnifiedError, WirelessBand, WirelessNetwork,
+  WirelessNetworkBuilder, WirelessNetworkSecurity, WirelessNetworkWpa, WirelessNetworkWpaMode,
+};
+
+/// Desired configuration of a site.
+///
+/// Objects are matched by name against the ones configured on the site.
+/// Attributes left out of the document are left untouched on existing
+/// objects, and take their default value on new ones.
+///
+/// # Example
+///
+/// ```yaml
+/// networks:
+///   - name: Employees
+///     purpose: corporate
+///     group: { lan: LAN1 }
+///     subnet: 10.0.20.1/24
+///     vlan: 20
+/// wireless_networks:
+///   - name: ACME Corp
+///     network: Employees
+///     ap_groups: [All APs]
+///     security: wpapsk
+///     wpa_mode: wpa2
+///     passphrase: correct horse battery staple
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DesiredState {
+  /// Wired networks
+  #[serde(default)]
+  pub networks: Vec<DesiredNetwork>,
+  /// Wireless networks
+  #[serde(default)]
+  pub wireless_networks: Vec<DesiredWirelessNetwork>,
+  /// RADIUS users
+  #[serde(default)]
+  pub radius_users: Vec<DesiredRadiusUser>,
+  /// Delete the objects of those kinds that are not listed in the document.
+  /// Objects the controller does not allow to delete (such as the default
+  /// network) and WAN networks are always kept.
+  #[serde(default)]
+  pub prune: bool,
+}
+
+/// Desired configuration of a wired network.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DesiredNetwork {
+  /// Network name
+  pub name: String,
+  /// Is the network enabled?
+  #[serde(default)]
+  pub enabled: Option<bool>,
+  /// Type of network
+  #[serde(default)]
+  pub purpose: Option<NetworkPurpose>,
+  /// Physical interface for the network
+  #[serde(default)]
+  pub group: Option<NetworkGroup>,
+  /// "Gateway/subnet" for the network (e.g. "10.0.0.254/24")
+  #[serde(default)]
+  pub subnet: Option<IpNet>,
+  /// Domain name for the network
+  #[serde(default)]
+  pub domain: Option<String>,
+  /// VLAN ID for the network, enables VLAN tagging
+  #[serde(default)]
+  pub vlan: Option<u16>,
+  /// DHCP server configuration, enables DHCP on the network
+  #[serde(default)]
+  pub dhcp: Option<DesiredDhcp>,
+}
+
+/// Desired DHCP configuration of a wired network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesiredDhcp {
+  /// First address of the DHCP range
+  pub start: IpAddr,
+  /// Last address of the DHCP range
+  pub end: IpAddr,
+  /// Lease time, in seconds
+  #[serde(default)]
+  pub lease_time: Option<u64>,
+}
+
+/// Desired configuration of a wireless network.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DesiredWirelessNetwork {
+  /// SSID
+  pub name: String,
+  /// Is the wireless network enabled?
+  #[serde(default)]
+  pub enabled: Option<bool>,
+  /// Name of the wired network the wireless network is attached to
+  #[serde(default)]
+  pub network: Option<String>,
+  /// Names of the AP groups broadcasting the wireless network
+  #[serde(default)]
+  pub ap_groups: Option<Vec<String>>,
+  /// Wireless band the network is broadcast on
+  #[serde(default)]
+  pub band: Option<WirelessBand>,
+  /// Type of security
+  #[serde(default)]
+  pub security: Option<WirelessNetworkSecurity>,
+  /// Supported WPA versions
+  #[serde(default)]
+  pub wpa_mode: Option<WirelessNetworkWpaMode>,
+  /// Passphrase, if applicable
+  #[serde(default)]
+  pub passphrase: Option<String>,
+  /// VLAN ID for traffic on this network
+  #[serde(default)]
+  pub vlan: Option<u16>,
+  /// Is the SSID advertised?
+  #[serde(default)]
+  pub advertised: Option<bool>,
+  /// Name of the RADIUS profile used for 802.1x
+  #[serde(default)]
+  pub radius_profile: Option<String>,
+}
+
+/// Desired configuration of a RADIUS user.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DesiredRadiusUser {
+  /// Username
+  pub name: String,
+  /// Cleartext password, required for new users
+  #[serde(default)]
+  pub password: Option<String>,
+  /// VLAN ID used to tag traffic from this user
+  #[serde(default)]
+  pub vlan: Option<u16>,
+  /// Protocol used for the tunnel
+  #[serde(default)]
+  pub tunnel_type: Option<TunnelType>,
+  /// Low-level protocol used for this tunnel medium
+  #[serde(default)]
+  pub tunnel_medium_type: Option<TunnelMediumType>,
+}
+
+/// Kind of change planned on an object.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+  Create,
+  Update,
+  Delete,
+}
+
+/// Kind of object a change is planned on.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+  Network,
+  WirelessNetwork,
+  RadiusUser,
+}
+
+impl fmt::Display for ResourceKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let value = match self {
+      Self::Network => "network",
+      Self::WirelessNetwork => "wireless network",
+      Self::RadiusUser => "RADIUS user",
+    };
+
+    write!(f, "{}", value)
+  }
+}
+
+/// Change planned on a single object.
+#[derive(Debug)]
+pub struct PlannedChange {
+  /// Kind of change
+  pub kind: ChangeKind,
+  /// Kind of object
+  pub resource: ResourceKind,
+  /// Name of the object
+  pub name: String,
+  /// Attributes changed on the object (empty for deletions)
+  pub fields: Vec<FieldChange>,
+
+  action: Action,
+}
+
+#[derive(Debug)]
+enum Action {
+  Network(Network),
+  // The name of the network is kept when it is created by the same plan, and
+  // its ID is only known once it is applied.
+  WirelessNetwork(WirelessNetwork, Option<String>),
+  RadiusUser(RadiusUser),
+}
+
+/// Changes needed to bring a site to its desired state.
+///
+/// Changes are ordered so that they can be applied in sequence: networks are
+/// created before the wireless networks attached to them, and deleted after.
+/// The plan can be displayed to be reviewed before being applied.
+#[derive(Debug)]
+pub struct Plan {
+  /// Site the plan applies to
+  pub site: String,
+  /// Planned changes, in the order they will be applied
+  pub changes: Vec<PlannedChange>,
+}
+
+impl Plan {
+  /// Is the site already in its desired state?
+  pub fn is_empty(&self) -> bool {
+    self.changes.is_empty()
+  }
+
+  /// Apply the planned changes.
+  ///
+  /// Changes are applied in order, and application stops at the first
+  /// failure, leaving the previous changes in place. Planning again will
+  /// only list the remaining changes.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let plan = unifi.plan("default", &desired).await?;
+  ///
+  /// println!("{}", plan);
+  /// plan.apply().await?;
+  /// ```
+  pub async fn apply(self) -> Result<(), UnifiedError> {
+    let mut created_networks = HashMap::new();
+
+    for change in self.changes {
+      match (change.kind, change.action) {
+        (ChangeKind::Create, Action::Network(network)) => {
+          let network = network.create().await?;
+
+          created_networks.insert(network.name.clone(), network.id);
+        }
+        (ChangeKind::Update, Action::Network(network)) => network.update().await?,
+        (ChangeKind::Delete, Action::Network(network)) => network.delete().await?,
+
+        (kind, Action::WirelessNetwork(mut wlan, pending)) => {
+          resolve_pending(&mut wlan, pending, &created_networks)?;
+
+          match kind {
+            ChangeKind::Create => {
+              WirelessNetworkBuilder { network: wlan }.build()?.create().await?;
+            }
+            ChangeKind::Update => wlan.update().await?,
+            ChangeKind::Delete => wlan.delete().await?,
+          }
+        }
+
+        (ChangeKind::Create, Action::RadiusUser(user)) => {
+          user.create().await?;
+        }
+        (ChangeKind::Update, Action::RadiusUser(user)) => user.update().await?,
+        (ChangeKind::Delete, Action::RadiusUser(user)) => user.delete().await?,
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl fmt::Display for Plan {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.changes.is_empty() {
+      return writeln!(f, "No changes on site {}.", self.site);
+    }
+
+    for change in &self.changes {
+      let marker = match change.kind {
+        ChangeKind::Create => '+',
+        ChangeKind::Update => '~',
+        ChangeKind::Delete => '-',
+      };
+
+      writeln!(f, "{} {} \"{}\"", marker, change.resource, change.name)?;
+
+      for field in &change.fields {
+        match change.kind {
+          ChangeKind::Create => writeln!(f, "    {}: {}", field.field, display_value(&field.field, &field.after))?,
+          _ => writeln!(
+            f,
+            "    {}: {} -> {}",
+            field.field,
+            display_value(&field.field, &field.before),
+            display_value(&field.field, &field.after)
+          )?,
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Attach a wireless network to a network created earlier in the plan.
+fn resolve_pending(wlan: &mut WirelessNetwork, pending: Option<String>, created_networks: &HashMap<String, String>) -> Result<(), UnifiedError> {
+  if let Some(name) = pending {
+    match created_networks.get(&name) {
+      Some(id) => wlan.network = Some(id.clone()),
+      None => return Err(UnifiedError::NoSuchObject(format!("network \"{}\"", name))),
+    }
+  }
+
+  Ok(())
+}
+
+/// Render an attribute value in a plan, hiding secrets.
+fn display_value(field: &str, value: &Value) -> String {
+  match value {
+    Value::Null => "null".to_string(),
+    _ if field.starts_with("x_") => "(sensitive)".to_string(),
+    _ => value.to_string(),
+  }
+}
+
+impl DesiredNetwork {
+  fn apply_to(&self, network: &mut Network) {
+    if let Some(enabled) = self.enabled {
+      network.enabled = enabled;
+    }
+    if let Some(purpose) = self.purpose {
+      network.purpose = purpose;
+    }
+    if let Some(group) = &self.group {
+      network.group = group.clone();
+    }
+    if let Some(subnet) = self.subnet {
+      network.subnet = Some(subnet);
+    }
+    if let Some(domain) = &self.domain {
+      network.domain = Some(domain.clone());
+    }
+    if let Some(vlan) = self.vlan {
+      network.vlan_enabled = true;
+      network.vlan = Some(vlan);
+    }
+    if let Some(dhcp) = &self.dhcp {
+      network.dhcp = Some(NetworkDhcp {
+        enabled: true,
+        start: Some(dhcp.start),
+        end: Some(dhcp.end),
+        lease_duration: dhcp.lease_time.map(Duration::from_secs),
+      });
+    }
+  }
+}
+
+impl DesiredRadiusUser {
+  fn apply_to(&self, user: &mut RadiusUser) {
+    if let Some(password) = &self.password {
+      user.password = password.clone();
+    }
+    if let Some(vlan) = self.vlan {
+      user.vlan = Some(vlan);
+    }
+    if let Some(tunnel_type) = self.tunnel_type {
+      user.tunnel_type = Some(tunnel_type);
+    }
+    if let Some(tunnel_medium_type) = self.tunnel_medium_type {
+      user.tunnel_medium_type = Some(tunnel_medium_type);
+    }
+  }
+}
+
+/// IDs of the objects wireless networks refer to, by name.
+struct References {
+  networks: HashMap<String, String>,
+  pending_networks: Vec<String>,
+  ap_groups: HashMap<String, String>,
+  radius_profiles: HashMap<String, String>,
+}
+
+impl DesiredWirelessNetwork {
+  /// Apply the desired attributes, returning the name of the network to
+  /// attach the wireless network to once it is created.
+  fn apply_to(&self, wlan: &mut WirelessNetwork, refs: &References) -> Result<Option<String>, UnifiedError> {
+    let mut pending = None;
+
+    if let Some(enabled) = self.enabled {
+      wlan.enabled = enabled;
+    }
+    if let Some(network) = &self.network {
+      match refs.networks.get(network) {
+        Some(id) => wlan.network = Some(id.clone()),
+        None if refs.pending_networks.contains(network) => pending = Some(network.clone()),
+        None => return Err(UnifiedError::NoSuchObject(format!("network \"{}\"", network))),
+      }
+    }
+    if let Some(groups) = &self.ap_groups {
+      wlan.ap_groups = groups
+        .iter()
+        .map(|group| refs.ap_groups.get(group).cloned().ok_or_else(|| UnifiedError::NoSuchObject(format!("AP group \"{}\"", group))))
+        .collect::<Result<_, _>>()?;
+    }
+    if let Some(band) = self.band {
+      wlan.band = Some(band);
+    }
+    if let Some(security) = self.security {
+      wlan.security = security;
+    }
+    if let Some(mode) = self.wpa_mode {
+      wlan.wpa = Some(WirelessNetworkWpa { mode, encryption: "ccmp".to_string() });
+    }
+    if let Some(passphrase) = &self.passphrase {
+      wlan.passphrase = Some(passphrase.clone());
+    }
+    if let Some(vlan) = self.vlan {
+      wlan.vlan = Some(vlan);
+    }
+    if let Some(advertised) = self.advertised {
+      wlan.advertised = advertised;
+    }
+    if let Some(profile) = &self.radius_profile {
+      match refs.radius_profiles.get(profile) {
+        Some(id) => wlan.radius_profile = Some(id.clone()),
+        None => return Err(UnifiedError::NoSuchObject(format!("RADIUS profile \"{}\"", profile))),
+      }
+    }
+
+    Ok(pending)
+  }
+}
+
+impl Unified {
+  /// Compute the changes needed to bring a site to its desired state.
+  ///
+  /// Networks, wireless networks and RADIUS users are matched by name. Names
+  /// of networks, AP groups and RADIUS profiles referenced by wireless
+  /// networks are resolved to their IDs. New objects are validated like
+  /// their builders would.
+  ///
+  /// Nothing is changed on the controller until the returned plan is
+  /// applied.
+  ///
+  /// # Arguments
+  ///
+  ///  * `site`    - Name of the site to reconcile
+  ///  * `desired` - Desired state of the site
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let desired: DesiredState = serde_yaml::from_str(&document)?;
+  /// let plan = unifi.plan("default", &desired).await?;
+  ///
+  /// println!("{}", plan);
+  /// ```
+  pub async fn plan(&self, site: &str, desired: &DesiredState) -> Result<Plan, UnifiedError> {
+    let current = Current {
+      networks: self.networks(site).await?,
+      wireless_networks: self.wireless_networks(site).await?,
+      radius_users: self.radius_users(site).await?,
+      ap_groups: self.ap_groups(site).await?.into_iter().map(|group| (group.name, group.id)).collect(),
+      radius_profiles: self.radius_profiles(site).await?.into_iter().map(|profile| (profile.name, profile.id)).collect(),
+    };
+
+    build_plan(self, site, desired, current)
+  }
+}
+
+/// Objects configured on a site, and IDs of the AP groups and RADIUS profiles
+/// by name.
+struct Current {
+  networks: Vec<Network>,
+  wireless_networks: Vec<WirelessNetwork>,
+  radius_users: Vec<RadiusUser>,
+  ap_groups: HashMap<String, String>,
+  radius_profiles: HashMap<String, String>,
+}
+
+/// Match the desired state against the objects configured on a site, and
+/// order the resulting changes.
+fn build_plan(unified: &Unified, site: &str, desired: &DesiredState, current: Current) -> Result<Plan, UnifiedError> {
+  let Current {
+    networks,
+    wireless_networks: wlans,
+    radius_users: users,
+    ap_groups,
+    radius_profiles,
+  } = current;
+
+  let refs = References {
+    networks: networks.iter().map(|network| (network.name.clone(), network.id.clone())).collect(),
+    pending_networks: desired
+      .networks
+      .iter()
+      .filter(|wanted| !networks.iter().any(|network| network.name == wanted.name))
+      .map(|wanted| wanted.name.clone())
+      .collect(),
+    ap_groups,
+    radius_profiles,
+  };
+
+  let mut changes = vec![];
+
+  for wanted in &desired.networks {
+    let (kind, network) = match networks.iter().find(|network| network.name == wanted.name) {
+      Some(network) => {
+        let mut network = network.clone();
+        wanted.apply_to(&mut network);
+
+        (ChangeKind::Update, network)
+      }
+
+      None => {
+        let mut builder = Network::builder(unified, site, &wanted.name);
+        wanted.apply_to(&mut builder.network);
+
+        (ChangeKind::Create, builder.build()?)
+      }
+    };
+
+    push_change(&mut changes, kind, ResourceKind::Network, &wanted.name, network.changes(), Action::Network(network));
+  }
+
+  for wanted in &desired.wireless_networks {
+    let (kind, mut wlan) = match wlans.iter().find(|wlan| wlan.name == wanted.name) {
+      Some(wlan) => (ChangeKind::Update, wlan.clone()),
+      None => (ChangeKind::Create, WirelessNetwork::builder(unified, site, &wanted.name).network),
+    };
+
+    let before = wlan.network.clone();
+    let pending = wanted.apply_to(&mut wlan, &refs)?;
+    let mut fields = wlan.changes();
+
+    if let Some(name) = &pending {
+      fields.retain(|field| field.field != "networkconf_id");
+      fields.push(FieldChange {
+        field: "networkconf_id".to_string(),
+        before: before.map(Value::String).unwrap_or(Value::Null),
+        after: Value::String(format!("(network \"{}\")", name)),
+      });
+      fields.sort_by(|a, b| a.field.cmp(&b.field));
+    }
+
+    if kind == ChangeKind::Create {
+      let mut check = wlan.clone();
+      check.network = check.network.or_else(|| pending.clone());
+
+      WirelessNetworkBuilder { network: check }.build()?;
+    }
+
+    push_change(&mut changes, kind, ResourceKind::WirelessNetwork, &wanted.name, fields, Action::WirelessNetwork(wlan, pending));
+  }
+
+  for wanted in &desired.radius_users {
+    let (kind, user) = match users.iter().find(|user| user.name == wanted.name) {
+      Some(user) => {
+        let mut user = user.clone();
+        wanted.apply_to(&mut user);
+
+        (ChangeKind::Update, user)
+      }
+
+      None => {
+        let password = wanted.password.as_deref().ok_or_else(|| UnifiedError::MissingAttribute("password".to_string()))?;
+        let mut builder = RadiusUser::builder(unified, site, &wanted.name, password);
+        wanted.apply_to(&mut builder.user);
+
+        (ChangeKind::Create, builder.build()?)
+      }
+    };
+
+    push_change(&mut changes, kind, ResourceKind::RadiusUser, &wanted.name, user.changes(), Action::RadiusUser(user));
+  }
+
+  if desired.prune {
+    for wlan in wlans
+      .into_iter()
+      .filter(|wlan| !is_protected(wlan.raw()) && !desired.wireless_networks.iter().any(|wanted| wanted.name == wlan.name))
+    {
+      push_change(
+        &mut changes,
+        ChangeKind::Delete,
+        ResourceKind::WirelessNetwork,
+        &wlan.name.clone(),
+        vec![],
+        Action::WirelessNetwork(wlan, None),
+      );
+    }
+    for user in users
+      .into_iter()
+      .filter(|user| !is_protected(user.raw()) && !desired.radius_users.iter().any(|wanted| wanted.name == user.name))
+    {
+      push_change(&mut changes, ChangeKind::Delete, ResourceKind::RadiusUser, &user.name.clone(), vec![], Action::RadiusUser(user));
+    }
+    for network in networks
+      .into_iter()
+      .filter(|network| !is_protected(network.raw()) && !matches!(network.purpose, NetworkPurpose::Wan) && !desired.networks.iter().any(|wanted| wanted.name == network.name))
+    {
+      push_change(&mut changes, ChangeKind::Delete, ResourceKind::Network, &network.name.clone(), vec![], Action::Network(network));
+    }
+  }
+
+  Ok(Plan { site: site.to_string(), changes })
+}
+
+/// Record a planned change, unless it is an update that changes nothing.
+fn push_change(changes: &mut Vec<PlannedChange>, kind: ChangeKind, resource: ResourceKind, name: &str, fields: Vec<FieldChange>, action: Action) {
+  if kind == ChangeKind::Update && fields.is_empty() {
+    return;
+  }
+
+  changes.push(PlannedChange {
+    kind,
+    resource,
+    name: name.to_string(),
+    fields,
+    action,
+  });
+}
+
+/// Whether the controller forbids deleting an object.
+fn is_protected(raw: &Map<String, Value>) -> bool {
+  raw.get("attr_no_delete").and_then(Value::as_bool).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use serde_json::{json, Map, Value};
+
+  use super::{build_plan, resolve_pending, Action, ChangeKind, Current, DesiredNetwork, DesiredRadiusUser, DesiredState, DesiredWirelessNetwork, Plan, ResourceKind};
+  use crate::{Network, NetworkGroup, NetworkPurpose, RadiusUser, RestResource, Unified, UnifiedError, WirelessNetwork, WirelessNetworkSecurity};
+
+  fn object(value: Value) -> Map<String, Value> {
+    match value {
+      Value::Object(object) => object,
+      _ => unreachable!(),
+    }
+  }
+
+  fn network(id: &str, name: &str) -> Value {
+    json!({ "_id": id, "name": name, "purpose": "corporate", "networkgroup": "LAN1", "ip_subnet": "10.0.0.1/24" })
+  }
+
+  fn wlan(id: &str, name: &str, network: &str) -> Value {
+    json!({ "_id": id, "name": name, "enabled": true, "networkconf_id": network, "ap_group_ids": ["g1"], "security": "open" })
+  }
+
+  fn current(unified: &Unified, networks: Vec<Value>, wlans: Vec<Value>, users: Vec<Value>) -> Current {
+    Current {
+      networks: networks.into_iter().map(|value| Network::from_json(unified, "default", object(value)).unwrap()).collect(),
+      wireless_networks: wlans.into_iter().map(|value| WirelessNetwork::from_json(unified, "default", object(value)).unwrap()).collect(),
+      radius_users: users.into_iter().map(|value| RadiusUser::from_json(unified, "default", object(value)).unwrap()).collect(),
+      ap_groups: vec![("All APs".to_string(), "g1".to_string())].into_iter().collect(),
+      radius_profiles: HashMap::new(),
+    }
+  }
+
+  fn summary(plan: &Plan) -> Vec<(ChangeKind, ResourceKind, &str)> {
+    plan.changes.iter().map(|change| (change.kind, change.resource, change.name.as_str())).collect()
+  }
+
+  #[test]
+  fn matches_existing_objects_by_name() {
+    let unified = Unified::new("unifi.test");
+    let current = current(&unified, vec![network("n1", "Employees"), network("n2", "Servers")], vec![], vec![]);
+
+    let desired = DesiredState {
+      networks: vec![
+        DesiredNetwork {
+          name: "Employees".to_string(),
+          vlan: Some(20),
+          ..Default::default()
+        },
+        DesiredNetwork {
+          name: "Servers".to_string(),
+          ..Default::default()
+        },
+      ],
+      ..Default::default()
+    };
+
+    let plan = build_plan(&unified, "default", &desired, current).unwrap();
+
+    assert_eq!(summary(&plan), vec![(ChangeKind::Update, ResourceKind::Network, "Employees")]);
+    assert_eq!(plan.changes[0].fields.iter().map(|field| field.field.as_str()).collect::<Vec<_>>(), vec!["vlan", "vlan_enabled"]);
+  }
+
+  #[test]
+  fn creates_networks_before_wireless_networks() {
+    let unified = Unified::new("unifi.test");
+    let current = current(&unified, vec![], vec![], vec![]);
+
+    let desired = DesiredState {
+      networks: vec![DesiredNetwork {
+        name: "Employees".to_string(),
+        purpose: Some(NetworkPurpose::Corporate),
+        group: Some(NetworkGroup::Lan("LAN1".to_string())),
+        subnet: Some("10.0.20.1/24".parse().unwrap()),
+        ..Default::default()
+      }],
+      wireless_networks: vec![DesiredWirelessNetwork {
+        name: "ACME Corp".to_string(),
+        network: Some("Employees".to_string()),
+        ap_groups: Some(vec!["All APs".to_string()]),
+        security: Some(WirelessNetworkSecurity::Open),
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    let plan = build_plan(&unified, "default", &desired, current).unwrap();
+
+    assert_eq!(
+      summary(&plan),
+      vec![
+        (ChangeKind::Create, ResourceKind::Network, "Employees"),
+        (ChangeKind::Create, ResourceKind::WirelessNetwork, "ACME Corp")
+      ]
+    );
+
+    match &plan.changes[1].action {
+      Action::WirelessNetwork(wlan, pending) => {
+        assert_eq!(wlan.network, None);
+        assert_eq!(pending.as_deref(), Some("Employees"));
+      }
+      _ => panic!("expected a wireless network"),
+    }
+  }
+
+  #[test]
+  fn resolves_pending_networks_at_apply_time() {
+    let unified = Unified::new("unifi.test");
+    let mut wlan = WirelessNetwork::builder(&unified, "default", "ACME Corp").network;
+    let created = vec![("Employees".to_string(), "n1".to_string())].into_iter().collect::<HashMap<_, _>>();
+
+    resolve_pending(&mut wlan, None, &created).unwrap();
+    assert_eq!(wlan.network, None);
+
+    resolve_pending(&mut wlan, Some("Employees".to_string()), &created).unwrap();
+    assert_eq!(wlan.network.as_deref(), Some("n1"));
+
+    assert!(matches!(resolve_pending(&mut wlan, Some("Guests".to_string()), &created), Err(UnifiedError::NoSuchObject(_))));
+  }
+
+  #[test]
+  fn prunes_networks_after_wireless_networks() {
+    let unified = Unified::new("unifi.test");
+    let current = current(
+      &unified,
+      vec![network("n1", "Employees")],
+      vec![wlan("w1", "ACME Corp", "n1")],
+      vec![json!({ "_id": "u1", "name": "joe", "x_password": "hunter2" })],
+    );
+
+    let desired = DesiredState { prune: true, ..Default::default() };
+    let plan = build_plan(&unified, "default", &desired, current).unwrap();
+
+    assert_eq!(
+      summary(&plan),
+      vec![
+        (ChangeKind::Delete, ResourceKind::WirelessNetwork, "ACME Corp"),
+        (ChangeKind::Delete, ResourceKind::RadiusUser, "joe"),
+        (ChangeKind::Delete, ResourceKind::Network, "Employees"),
+      ]
+    );
+  }
+
+  #[test]
+  fn prune_keeps_protected_and_wan_networks() {
+    let unified = Unified::new("unifi.test");
+
+    let mut default = network("n1", "Default");
+    default["attr_no_delete"] = json!(true);
+
+    let wan = json!({ "_id": "n2", "name": "Internet", "purpose": "wan", "wan_networkgroup": "WAN" });
+    let current = current(&unified, vec![default, wan, network("n3", "Legacy")], vec![], vec![]);
+
+    let desired = DesiredState { prune: true, ..Default::default() };
+    let plan = build_plan(&unified, "default", &desired, current).unwrap();
+
+    assert_eq!(summary(&plan), vec![(ChangeKind::Delete, ResourceKind::Network, "Legacy")]);
+  }
+
+  #[test]
+  fn display_masks_secrets() {
+    let unified = Unified::new("unifi.test");
+
+    let mut protected = wlan("w1", "ACME Corp", "n1");
+    protected["security"] = json!("wpapsk");
+    protected["wpa_mode"] = json!("wpa2");
+    protected["wpa_enc"] = json!("ccmp");
+    protected["x_passphrase"] = json!("old-passphrase");
+
+    let current = current(&unified, vec![network("n1", "Employees")], vec![protected], vec![]);
+
+    let desired = DesiredState {
+      wireless_networks: vec![DesiredWirelessNetwork {
+        name: "ACME Corp".to_string(),
+        passphrase: Some("new-passphrase".to_string()),
+        ..Default::default()
+      }],
+      radius_users: vec![DesiredRadiusUser {
+        name: "joe".to_string(),
+        password: Some("hunter2".to_string()),
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    let output = build_plan(&unified, "default", &desired, current).unwrap().to_string();
+
+    assert!(output.contains("x_passphrase: (sensitive) -> (sensitive)"));
+    assert!(output.contains("x_password: (sensitive)"));
+    assert!(!output.contains("old-passphrase"));
+    assert!(!output.contains("new-passphrase"));
+    assert!(!output.contains("hunter2"));
+  }
+}
@@ -0,0 +1,225 @@
+use reqwest::Method;
+use serde_json::{Map, Value};
+
+use crate::{
+  cache::Collection,
+  changes,
+  http::{ApiV1, ApiV1NoData},
+  Unified, UnifiedError,
+};
+
+/// Object stored in a `rest/*` collection of the controller.
+///
+/// Implementing this trait is enough to list, look up, create, update and
+/// delete objects of a collection through the generic methods of
+/// [`Unified`]. Conversion goes through the JSON representation of the
+/// objects, as read from and sent to the controller.
+pub trait RestResource: Sized + Send + Sync {
+  /// Name of the collection, as found after `rest/` (e.g. `networkconf`)
+  const COLLECTION: &'static str;
+
+  /// Collection to use when caching objects, if any
+  const CACHE: Option<Collection> = None;
+
+  /// Build an object from its JSON representation.
+  ///
+  /// # Arguments
+  ///
+  ///  * `unified` - Handle the object was read through
+  ///  * `site`    - Name of the site the object belongs to
+  ///  * `object`  - JSON object returned by the controller
+  fn from_json(unified: &Unified, site: &str, object: Map<String, Value>) -> Result<Self, UnifiedError>;
+
+  /// JSON representation of the attributes of the object to be sent to the
+  /// controller.
+  fn to_json(&self) -> Map<String, Value>;
+
+  /// Name of the site the object belongs to.
+  fn site(&self) -> &str;
+
+  /// Internal ID of the object.
+  fn id(&self) -> &str;
+
+  /// JSON representation of the object as it was loaded, as produced by
+  /// [`to_json()`](RestResource::to_json).
+  ///
+  /// When provided, only the attributes that changed since then are sent on
+  /// update, and conflicting changes can be detected.
+  fn loaded(&self) -> Option<&Map<String, Value>> {
+    None
+  }
+}
+
+impl Unified {
+  /// List all objects of a REST collection on the given site.
+  ///
+  /// # Arguments
+  ///
+  ///  * `site` - Name of the site to use
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let networks = unifi.list_resources::<Network>("default").await?;
+  /// ```
+  pub async fn list_resources<R>(&self, site: &str) -> Result<Vec<R>, UnifiedError>
+  where
+    R: RestResource,
+  {
+    let path = format!("/api/s/{}/rest/{}", site, R::COLLECTION);
+
+    let objects = match R::CACHE {
+      Some(collection) => self.cached::<ApiV1<Vec<Map<String, Value>>>, _>(Some(site), collection, &path).await?,
+      None => self.request::<ApiV1<Vec<Map<String, Value>>>>(Method::GET, &path).query().await?,
+    };
+
+    objects.into_iter().map(|object| R::from_json(self, site, object)).collect()
+  }
+
+  /// Find an object of a REST collection by its ID.
+  ///
+  /// # Arguments
+  ///
+  ///  * `site` - Name of the site to use
+  ///  * `id`   - Internal ID of the object
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let network = unifi.get_resource::<Network>("default", "6105dd31b49ca605191f4373").await?;
+  /// ```
+  pub async fn get_resource<R>(&self, site: &str, id: &str) -> Result<Option<R>, UnifiedError>
+  where
+    R: RestResource,
+  {
+    let object = self.lookup::<Map<String, Value>>(&format!("/api/s/{}/rest/{}/{}", site, R::COLLECTION, id)).await?;
+
+    object.map(|object| R::from_json(self, site, object)).transpose()
+  }
+
+  /// Create an object in a REST collection.
+  ///
+  /// The object is returned as created by the controller.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let network = unifi.create_resource(&network).await?;
+  /// ```
+  pub async fn create_resource<R>(&self, resource: &R) -> Result<R, UnifiedError>
+  where
+    R: RestResource,
+  {
+    let body = Value::Object(resource.to_json());
+
+    let created = self
+      .request::<ApiV1<Vec<Map<String, Value>>>>(Method::POST, &format!("/api/s/{}/rest/{}", resource.site(), R::COLLECTION))
+      .map(|r| r.json(&body))
+      .query()
+      .await?
+      .into_iter()
+      .next()
+      .ok_or(UnifiedError::Unknown)?;
+
+    self.invalidate_resources::<R>(resource.site());
+
+    R::from_json(self, resource.site(), created)
+  }
+
+  /// Update an object of a REST collection.
+  ///
+  /// If the object knows how it was loaded, only the changed attributes are
+  /// sent, and nothing is sent if none changed. If conflict checking is
+  /// enabled on the handle (see [`Unified::check_conflicts()`]), the update
+  /// fails with [`UnifiedError::Conflict`] when the object was modified on
+  /// the controller since it was loaded, with [`UnifiedError::NoSuchObject`]
+  /// when it was deleted there, and with [`UnifiedError::NotLoaded`] when it
+  /// was not loaded from the controller (e.g. deserialized).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// unifi.update_resource(&network).await?;
+  /// ```
+  pub async fn update_resource<R>(&self, resource: &R) -> Result<(), UnifiedError>
+  where
+    R: RestResource,
+  {
+    let path = format!("/api/s/{}/rest/{}/{}", resource.site(), R::COLLECTION, resource.id());
+    let loaded = resource.loaded().cloned().unwrap_or_default();
+    let changes = changes::diff(&loaded, &resource.to_json());
+
+    if changes.is_empty() {
+      return Ok(());
+    }
+
+    if self.inner.check_conflicts {
+      if loaded.is_empty() {
+        return Err(UnifiedError::NotLoaded { path });
+      }
+
+      self.check_conflict::<R>(resource, &path, loaded).await?;
+    }
+
+    let body = changes::body(&changes);
+
+    self.request::<ApiV1NoData>(Method::PUT, &path).map(|r| r.json(&body)).query().await?;
+
+    self.invalidate_resources::<R>(resource.site());
+
+    Ok(())
+  }
+
+  /// Delete an object from a REST collection.
+  ///
+  /// # Arguments
+  ///
+  ///  * `site` - Name of the site to use
+  ///  * `id`   - Internal ID of the object
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// unifi.delete_resource::<Network>("default", "6105dd31b49ca605191f4373").await?;
+  /// ```
+  pub async fn delete_resource<R>(&self, site: &str, id: &str) -> Result<(), UnifiedError>
+  where
+    R: RestResource,
+  {
+    self.request::<ApiV1NoData>(Method::DELETE, &format!("/api/s/{}/rest/{}/{}", site, R::COLLECTION, id)).query().await?;
+
+    self.invalidate_resources::<R>(site);
+
+    Ok(())
+  }
+
+  /// Make sure an object was not modified on the controller since it was
+  /// loaded.
+  async fn check_conflict<R>(&self, resource: &R, path: &str, loaded: Map<String, Value>) -> Result<(), UnifiedError>
+  where
+    R: RestResource,
+  {
+    let current = self.get_resource::<R>(resource.site(), resource.id()).await?.map(|current| current.to_json());
+
+    match current {
+      Some(ref current) if *current == loaded => Ok(()),
+
+      Some(current) => Err(UnifiedError::Conflict {
+        path: path.to_string(),
+        loaded: Value::Object(loaded),
+        current: Value::Object(current),
+      }),
+
+      None => Err(UnifiedError::NoSuchObject(path.to_string())),
+    }
+  }
+
+  fn invalidate_resources<R>(&self, site: &str)
+  where
+    R: RestResource,
+  {
+    if let Some(collection) = R::CACHE {
+      self.invalidate(site, collection);
+    }
+  }
+}
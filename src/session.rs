@@ -0,0 +1,198 @@
+use std::{
+  ops::{Deref, DerefMut},
+  sync::PoisonError,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  http::Scheme,
+  unified::{Flavour, SessionState},
+  Unified, UnifiedError,
+};
+
+/// Authenticated session with a Unifi controller.
+///
+/// A session can be exported from an authenticated [`Unified`] handle,
+/// serialized to be stored somewhere, and later be used to rebuild a handle
+/// without logging in again.
+#[derive(Clone, Derivative, Serialize, Deserialize)]
+#[derivative(Debug)]
+pub struct Session {
+  pub(crate) scheme: Scheme,
+  pub(crate) host: String,
+  #[derivative(Debug = "ignore")]
+  pub(crate) cookies: String,
+  #[derivative(Debug = "ignore")]
+  pub(crate) csrf: String,
+  pub(crate) udm_pro: bool,
+  pub(crate) expires_at: Option<DateTime<Utc>>,
+}
+
+impl Session {
+  /// Hostname and port of the Unifi controller this session belongs to.
+  pub fn host(&self) -> &str {
+    &self.host
+  }
+
+  /// Date and time the session cookie expires, if the controller set one.
+  pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+    self.expires_at
+  }
+
+  /// Has the session cookie expired?
+  pub fn is_expired(&self) -> bool {
+    self.expires_at.map(|expires_at| expires_at <= Utc::now()).unwrap_or_default()
+  }
+}
+
+/// Handle that logs out of the controller when dropped.
+///
+/// Should be created through [`Unified::logout_on_drop`]. Since clones of a
+/// handle share their session, dropping the guard also logs them out. The logout is
+/// performed in the background, and only if the guard is dropped inside a
+/// Tokio runtime.
+pub struct SessionGuard {
+  unified: Option<Unified>,
+}
+
+impl SessionGuard {
+  /// Get back the wrapped handle, without logging out.
+  pub fn into_inner(mut self) -> Unified {
+    self.unified.take().expect("session guard should hold a handle")
+  }
+}
+
+impl Deref for SessionGuard {
+  type Target = Unified;
+
+  fn deref(&self) -> &Unified {
+    self.unified.as_ref().expect("session guard should hold a handle")
+  }
+}
+
+impl DerefMut for SessionGuard {
+  fn deref_mut(&mut self) -> &mut Unified {
+    self.unified.as_mut().expect("session guard should hold a handle")
+  }
+}
+
+impl Drop for SessionGuard {
+  fn drop(&mut self) {
+    if let (Some(unified), Ok(runtime)) = (self.unified.take(), tokio::runtime::Handle::try_current()) {
+      runtime.spawn(async move {
+        let _ = unified.logout().await;
+      });
+    }
+  }
+}
+
+impl Unified {
+  /// End the current session on the controller.
+  ///
+  /// The stored token and CSRF token are cleared, even if the controller
+  /// could not be reached.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// unifi.logout().await?;
+  /// ```
+  pub async fn logout(&self) -> Result<(), UnifiedError> {
+    let session = std::mem::take(&mut *self.inner.session.write().unwrap_or_else(PoisonError::into_inner));
+
+    if self.inner.api_key.is_some() || session.token.is_empty() {
+      return Ok(());
+    }
+
+    let path = match self.is_udm_pro() {
+      true => "/api/auth/logout",
+      false => "/api/logout",
+    };
+
+    let request = self
+      .http()?
+      .post(format!("{}://{}{}", self.inner.scheme.as_str(), self.inner.host, path))
+      .header("cookie", session.token);
+
+    let request = match self.is_udm_pro() {
+      true => request.header("x-csrf-token", session.csrf),
+      false => request,
+    };
+
+    let response = request.send().await?;
+
+    if !response.status().is_success() {
+      return Err(UnifiedError::HttpError {
+        status: response.status(),
+        path: path.to_string(),
+      });
+    }
+
+    Ok(())
+  }
+
+  /// Wrap the handle into a guard that will log out when dropped.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").auth("joe.shmoe", "mypassword").await?.logout_on_drop();
+  /// let networks = unifi.networks("default").await?;
+  /// ```
+  pub fn logout_on_drop(self) -> SessionGuard {
+    SessionGuard { unified: Some(self) }
+  }
+
+  /// Export the current session, to be reused later.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").auth("joe.shmoe", "mypassword").await?;
+  /// let session = serde_json::to_string(&unifi.session())?;
+  /// ```
+  pub fn session(&self) -> Session {
+    let state = self.session_state();
+
+    Session {
+      scheme: self.inner.scheme,
+      host: self.inner.host.clone(),
+      cookies: state.token,
+      csrf: state.csrf,
+      udm_pro: self.is_udm_pro(),
+      expires_at: state.expires_at,
+    }
+  }
+
+  /// Rebuild a Unified handle from a previously exported session.
+  ///
+  /// # Arguments
+  ///
+  ///  * `session` - Session exported through [`Unified::session`]
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let session: Session = serde_json::from_str(&cached)?;
+  /// let unifi = Unified::from_session(session);
+  /// ```
+  pub fn from_session(session: Session) -> Unified {
+    Unified::new(&session.host).configure(|inner| {
+      inner.scheme = session.scheme;
+
+      *inner.flavour.get_mut().unwrap_or_else(PoisonError::into_inner) = Flavour {
+        is_udm_pro: session.udm_pro,
+        detected: true,
+        version: None,
+      };
+
+      *inner.session.get_mut().unwrap_or_else(PoisonError::into_inner) = SessionState {
+        token: session.cookies,
+        csrf: session.csrf,
+        expires_at: session.expires_at,
+      };
+    })
+  }
+}
@@ -0,0 +1,238 @@
+use crate::{
+  alerts::Alert,
+  events::Event,
+  sites::types::{Site, SiteRef},
+  ApGroup, Client, ClientRef, Collection, CollisionPolicy, CopyReport, CopySelection, DesiredState, Device, DeviceRef, Network, NetworkBuilder, NetworkRef, Plan, RadiusProfile, RadiusUser,
+  RadiusUserBuilder, RadiusUserRef, SiteSnapshot, Unified, UnifiedError, WirelessNetwork, WirelessNetworkBuilder, WirelessNetworkRef,
+};
+
+/// Handle to a specific site on the controller.
+///
+/// All site-scoped operations can be performed on the handle without passing
+/// the name of the site around. A handle can be obtained with
+/// [`Unified::site_handle()`], which makes sure the site exists, or from an
+/// already listed [`Site`] with [`Site::handle()`].
+#[derive(Clone, Derivative)]
+#[derivative(Debug)]
+pub struct SiteHandle {
+  #[derivative(Debug = "ignore")]
+  pub(crate) unified: Unified,
+  pub(crate) site: Site,
+}
+
+impl Unified {
+  /// Get a handle to a site on the controller.
+  ///
+  /// Returns [`UnifiedError::NoSuchObject`] if no site matches the provided
+  /// ref.
+  ///
+  /// # Arguments
+  ///
+  ///  * `site_ref` - Attribute and value to use to look up the site
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let site = unifi.site_handle(SiteRef::Name("default")).await?;
+  /// let clients = site.clients().await?;
+  /// ```
+  pub async fn site_handle(&self, site_ref: SiteRef<'_>) -> Result<SiteHandle, UnifiedError> {
+    let name = match site_ref {
+      SiteRef::Id(id) => id,
+      SiteRef::Name(name) => name,
+      SiteRef::Description(description) => description,
+    };
+
+    match self.site(site_ref).await? {
+      Some(site) => Ok(site.handle(self)),
+      None => Err(UnifiedError::NoSuchObject(format!("site \"{}\"", name))),
+    }
+  }
+}
+
+impl Site {
+  /// Get a handle to this site.
+  ///
+  /// # Arguments
+  ///
+  ///  * `unified` - Handle to the controller the site was listed from
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// for site in unifi.sites().await? {
+  ///   let devices = site.handle(&unifi).devices().await?;
+  /// }
+  /// ```
+  pub fn handle(&self, unified: &Unified) -> SiteHandle {
+    SiteHandle {
+      unified: unified.clone(),
+      site: self.clone(),
+    }
+  }
+}
+
+impl SiteHandle {
+  /// Site this handle operates on.
+  pub fn site(&self) -> &Site {
+    &self.site
+  }
+
+  /// Slug ID of the site (called "name" in the controller).
+  pub fn name(&self) -> &str {
+    &self.site.name
+  }
+
+  /// Handle to the controller.
+  pub fn unified(&self) -> &Unified {
+    &self.unified
+  }
+
+  /// List all clients on the site.
+  ///
+  /// See [`Unified::clients()`].
+  pub async fn clients(&self) -> Result<Vec<Client>, UnifiedError> {
+    self.unified.clients(&self.site.name).await
+  }
+
+  /// Find a specific client on the site.
+  ///
+  /// See [`Unified::client()`].
+  pub async fn client(&self, client_ref: ClientRef<'_>) -> Result<Option<Client>, UnifiedError> {
+    self.unified.client(&self.site.name, client_ref).await
+  }
+
+  /// List all adopted devices on the site.
+  ///
+  /// See [`Unified::devices()`].
+  pub async fn devices(&self) -> Result<Vec<Device>, UnifiedError> {
+    self.unified.devices(&self.site.name).await
+  }
+
+  /// Find a specific device on the site.
+  ///
+  /// See [`Unified::device()`].
+  pub async fn device(&self, device_ref: DeviceRef<'_>) -> Result<Option<Device>, UnifiedError> {
+    self.unified.device(&self.site.name, device_ref).await
+  }
+
+  /// List all configured networks on the site.
+  ///
+  /// See [`Unified::networks()`].
+  pub async fn networks(&self) -> Result<Vec<Network>, UnifiedError> {
+    self.unified.networks(&self.site.name).await
+  }
+
+  /// Find a specific network on the site.
+  ///
+  /// See [`Unified::network()`].
+  pub async fn network(&self, network_ref: NetworkRef<'_>) -> Result<Option<Network>, UnifiedError> {
+    self.unified.network(&self.site.name, network_ref).await
+  }
+
+  /// Create a builder for a network on the site.
+  ///
+  /// See [`Network::builder()`].
+  pub fn network_builder(&self, name: &str) -> NetworkBuilder {
+    Network::builder(&self.unified, &self.site.name, name)
+  }
+
+  /// List all configured wireless networks on the site.
+  ///
+  /// See [`Unified::wireless_networks()`].
+  pub async fn wireless_networks(&self) -> Result<Vec<WirelessNetwork>, UnifiedError> {
+    self.unified.wireless_networks(&self.site.name).await
+  }
+
+  /// Find a specific wireless network on the site.
+  ///
+  /// See [`Unified::wireless_network()`].
+  pub async fn wireless_network(&self, network_ref: WirelessNetworkRef<'_>) -> Result<Option<WirelessNetwork>, UnifiedError> {
+    self.unified.wireless_network(&self.site.name, network_ref).await
+  }
+
+  /// Create a builder for a wireless network on the site.
+  ///
+  /// See [`WirelessNetwork::builder()`].
+  pub fn wireless_network_builder(&self, name: &str) -> WirelessNetworkBuilder {
+    WirelessNetwork::builder(&self.unified, &self.site.name, name)
+  }
+
+  /// List all access point groups on the site.
+  ///
+  /// See [`Unified::ap_groups()`].
+  pub async fn ap_groups(&self) -> Result<Vec<ApGroup>, UnifiedError> {
+    self.unified.ap_groups(&self.site.name).await
+  }
+
+  /// List all RADIUS profiles on the site.
+  ///
+  /// See [`Unified::radius_profiles()`].
+  pub async fn radius_profiles(&self) -> Result<Vec<RadiusProfile>, UnifiedError> {
+    self.unified.radius_profiles(&self.site.name).await
+  }
+
+  /// List all RADIUS users on the site.
+  ///
+  /// See [`Unified::radius_users()`].
+  pub async fn radius_users(&self) -> Result<Vec<RadiusUser>, UnifiedError> {
+    self.unified.radius_users(&self.site.name).await
+  }
+
+  /// Find a specific RADIUS user on the site.
+  ///
+  /// See [`Unified::radius_user()`].
+  pub async fn radius_user(&self, user_ref: RadiusUserRef<'_>) -> Result<Option<RadiusUser>, UnifiedError> {
+    self.unified.radius_user(&self.site.name, user_ref).await
+  }
+
+  /// Create a builder for a RADIUS user on the site.
+  ///
+  /// See [`RadiusUser::builder()`].
+  pub fn radius_user_builder(&self, name: &str, password: &str) -> RadiusUserBuilder {
+    RadiusUser::builder(&self.unified, &self.site.name, name, password)
+  }
+
+  /// List events that happened on the site.
+  ///
+  /// See [`Unified::events()`].
+  pub async fn events(&self, limit: Option<u64>) -> Result<Vec<Event>, UnifiedError> {
+    self.unified.events(&self.site.name, limit).await
+  }
+
+  /// List alerts raised on the site.
+  ///
+  /// See [`Unified::alerts()`].
+  pub async fn alerts(&self, limit: Option<u64>) -> Result<Vec<Alert>, UnifiedError> {
+    self.unified.alerts(&self.site.name, limit).await
+  }
+
+  /// Compute the changes needed to bring the site to its desired state.
+  ///
+  /// See [`Unified::plan()`].
+  pub async fn plan(&self, desired: &DesiredState) -> Result<Plan, UnifiedError> {
+    self.unified.plan(&self.site.name, desired).await
+  }
+
+  /// Take a snapshot of the configuration of the site.
+  ///
+  /// See [`Unified::snapshot()`].
+  pub async fn snapshot(&self, redact: bool) -> Result<SiteSnapshot, UnifiedError> {
+    self.unified.snapshot(&self.site.name, redact).await
+  }
+
+  /// Discard the cached objects of a collection on the site.
+  ///
+  /// See [`Unified::invalidate()`].
+  pub fn invalidate(&self, collection: Collection) {
+    self.unified.invalidate(&self.site.name, collection)
+  }
+
+  /// Copy objects from the site to another one, possibly on another
+  /// controller.
+  ///
+  /// See [`Unified::copy_to()`].
+  pub async fn copy_to(&self, target: &SiteHandle, selection: &CopySelection, policy: CollisionPolicy) -> Result<CopyReport, UnifiedError> {
+    self.unified.copy_to(&self.site.name, &target.unified, &target.site.name, selection, policy).await
+  }
+}
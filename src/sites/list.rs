@@ -1,6 +1,4 @@
-use reqwest::Method;
-
-use crate::{http::ApiV1, sites::types::*, Unified, UnifiedError};
+use crate::{cache::Collection, http::ApiV1, sites::types::*, Unified, UnifiedError};
 
 impl Unified {
   /// List all configured sites on the controller.
@@ -15,7 +13,7 @@ impl Unified {
   /// let sites = unifi.sites().await?;
   /// ```
   pub async fn sites(&self) -> Result<Vec<Site>, UnifiedError> {
-    let response = self.request::<ApiV1<Vec<RemoteSite>>>(Method::GET, "/api/stat/sites").query().await?;
+    let response = self.cached::<ApiV1<Vec<RemoteSite>>, _>(None, Collection::Sites, "/api/stat/sites").await?;
 
     let sites = response
       .into_iter()
@@ -1,3 +1,4 @@
+pub(crate) mod handle;
 pub(crate) mod types;
 
 mod list;
@@ -1,6 +1,6 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub(super) struct RemoteSite {
   #[serde(rename = "_id")]
   pub id: String,
@@ -11,7 +11,7 @@ pub(super) struct RemoteSite {
   pub health: Vec<RemoteSiteHealth>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub(super) struct RemoteSiteHealth {
   pub subsystem: String,
   pub status: String,
@@ -28,7 +28,7 @@ pub enum SiteRef<'r> {
 }
 
 /// A location where Unifi hardware operates.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Site {
   /// Internal ID
   pub id: String,
@@ -43,7 +43,7 @@ pub struct Site {
 }
 
 /// Various data about overall health of a Unifi site.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SiteHealth {
   /// State of the Internet access
   pub www: bool,
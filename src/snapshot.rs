@@ -0,0 +1,382 @@
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{changes::FieldChange, ApiVersion, ChangeKind, Unified, UnifiedError};
+
+/// Version of the snapshot format produced by this crate.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+const REDACTED: &str = "(redacted)";
+
+/// Point-in-time copy of the configuration of a site.
+///
+/// Objects are kept as returned by the controller, including the attributes
+/// that are not modelled by this crate. A snapshot serializes to a single JSON
+/// document, and can be diffed against another one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiteSnapshot {
+  /// Version of the snapshot format
+  pub version: u32,
+  /// Name of the site the snapshot was taken on
+  pub site: String,
+  /// Time at which the snapshot was taken
+  pub taken_at: DateTime<Utc>,
+  /// Were secrets removed from the snapshot?
+  pub redacted: bool,
+
+  /// Wired networks
+  pub networks: Vec<Map<String, Value>>,
+  /// Wireless networks
+  pub wireless_networks: Vec<Map<String, Value>>,
+  /// Access Point groups
+  pub ap_groups: Vec<Map<String, Value>>,
+  /// RADIUS profiles
+  pub radius_profiles: Vec<Map<String, Value>>,
+  /// RADIUS users
+  pub radius_users: Vec<Map<String, Value>>,
+  /// Switch port profiles
+  pub port_profiles: Vec<Map<String, Value>>,
+  /// Firewall rules
+  pub firewall_rules: Vec<Map<String, Value>>,
+  /// Site settings, one object per section
+  pub settings: Vec<Map<String, Value>>,
+}
+
+/// Difference on a single object between two snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotChange {
+  /// Collection the object belongs to (e.g. `networks`)
+  pub collection: String,
+  /// Was the object created, updated or deleted?
+  pub kind: ChangeKind,
+  /// Identifier of the object (its ID, or its key for settings)
+  pub id: String,
+  /// Name of the object, if it has one
+  pub name: Option<String>,
+  /// Attributes that differ, with nested attributes joined by dots (empty
+  /// for created and deleted objects)
+  pub fields: Vec<FieldChange>,
+}
+
+impl Unified {
+  /// Take a snapshot of the configuration of a site.
+  ///
+  /// All collections are read from the controller, bypassing the cache. When
+  /// `redact` is set, the value of every secret attribute (those prefixed
+  /// with `x_`, such as passphrases and passwords) is replaced.
+  ///
+  /// # Arguments
+  ///
+  ///  * `site`   - Name of the site to use
+  ///  * `redact` - Whether to remove secrets from the snapshot
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let snapshot = unifi.snapshot("default", true).await?;
+  ///
+  /// std::fs::write("default.json", serde_json::to_string_pretty(&snapshot)?)?;
+  /// ```
+  pub async fn snapshot(&self, site: &str, redact: bool) -> Result<SiteSnapshot, UnifiedError> {
+    let mut snapshot = SiteSnapshot {
+      version: SNAPSHOT_VERSION,
+      site: site.to_string(),
+      taken_at: Utc::now(),
+      redacted: false,
+
+      networks: self.raw(Method::GET, &format!("/api/s/{}/rest/networkconf", site), ApiVersion::V1, None).await?,
+      wireless_networks: self.raw(Method::GET, &format!("/api/s/{}/rest/wlanconf", site), ApiVersion::V1, None).await?,
+      ap_groups: self.raw(Method::GET, &format!("/v2/api/site/{}/apgroups", site), ApiVersion::V2, None).await?,
+      radius_profiles: self.raw(Method::GET, &format!("/api/s/{}/rest/radiusprofile", site), ApiVersion::V1, None).await?,
+      radius_users: self.raw(Method::GET, &format!("/api/s/{}/rest/account", site), ApiVersion::V1, None).await?,
+      port_profiles: self.raw(Method::GET, &format!("/api/s/{}/rest/portconf", site), ApiVersion::V1, None).await?,
+      firewall_rules: self.raw(Method::GET, &format!("/api/s/{}/rest/firewallrule", site), ApiVersion::V1, None).await?,
+      settings: self.raw(Method::GET, &format!("/api/s/{}/get/setting", site), ApiVersion::V1, None).await?,
+    };
+
+    if redact {
+      snapshot.redact();
+    }
+
+    Ok(snapshot)
+  }
+}
+
+impl SiteSnapshot {
+  /// Parse a snapshot from its JSON representation.
+  ///
+  /// Returns [`UnifiedError::UnsupportedVersion`] if the snapshot was
+  /// produced in a format this crate does not know about.
+  pub fn from_json(json: &str) -> Result<SiteSnapshot, UnifiedError> {
+    let snapshot: SiteSnapshot = serde_json::from_str(json).map_err(UnifiedError::InvalidResponse)?;
+
+    if snapshot.version != SNAPSHOT_VERSION {
+      return Err(UnifiedError::UnsupportedVersion(snapshot.version));
+    }
+
+    Ok(snapshot)
+  }
+
+  /// Replace the value of every secret attribute in the snapshot.
+  pub fn redact(&mut self) {
+    for objects in self.collections_mut() {
+      for object in objects.iter_mut() {
+        redact(object);
+      }
+    }
+
+    self.redacted = true;
+  }
+
+  /// List the differences between this snapshot and a later one.
+  ///
+  /// Objects are matched by ID (or by key for settings), so both snapshots
+  /// should be taken on the same site. Nested objects are compared attribute
+  /// by attribute, while lists are compared as a whole.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// for change in before.diff(&after) {
+  ///   println!("{:?} {} {}", change.kind, change.collection, change.name.unwrap_or(change.id));
+  /// }
+  /// ```
+  pub fn diff(&self, later: &SiteSnapshot) -> Vec<SnapshotChange> {
+    self
+      .collections()
+      .into_iter()
+      .zip(later.collections())
+      .flat_map(|((collection, before), (_, after))| diff_collection(collection, before, after))
+      .collect()
+  }
+
+  fn collections(&self) -> Vec<(&'static str, &Vec<Map<String, Value>>)> {
+    vec![
+      ("networks", &self.networks),
+      ("wireless_networks", &self.wireless_networks),
+      ("ap_groups", &self.ap_groups),
+      ("radius_profiles", &self.radius_profiles),
+      ("radius_users", &self.radius_users),
+      ("port_profiles", &self.port_profiles),
+      ("firewall_rules", &self.firewall_rules),
+      ("settings", &self.settings),
+    ]
+  }
+
+  fn collections_mut(&mut self) -> Vec<&mut Vec<Map<String, Value>>> {
+    vec![
+      &mut self.networks,
+      &mut self.wireless_networks,
+      &mut self.ap_groups,
+      &mut self.radius_profiles,
+      &mut self.radius_users,
+      &mut self.port_profiles,
+      &mut self.firewall_rules,
+      &mut self.settings,
+    ]
+  }
+}
+
+/// Replace the values of secret attributes, at any depth.
+fn redact(object: &mut Map<String, Value>) {
+  for (key, value) in object.iter_mut() {
+    match value {
+      Value::Null => {}
+      _ if key.starts_with("x_") => *value = Value::String(REDACTED.to_string()),
+      Value::Object(nested) => redact(nested),
+      Value::Array(items) => {
+        for item in items.iter_mut() {
+          if let Value::Object(nested) = item {
+            redact(nested);
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Identifier used to match an object across snapshots.
+fn identity(collection: &str, object: &Map<String, Value>) -> String {
+  let key = match collection {
+    "settings" => "key",
+    _ => "_id",
+  };
+
+  object.get(key).and_then(Value::as_str).unwrap_or_default().to_string()
+}
+
+fn diff_collection(collection: &str, before: &[Map<String, Value>], after: &[Map<String, Value>]) -> Vec<SnapshotChange> {
+  let change = |kind, object: &Map<String, Value>, fields| SnapshotChange {
+    collection: collection.to_string(),
+    kind,
+    id: identity(collection, object),
+    name: object.get("name").and_then(Value::as_str).map(ToString::to_string),
+    fields,
+  };
+
+  let mut changes = vec![];
+
+  for old in before {
+    match after.iter().find(|new| identity(collection, new) == identity(collection, old)) {
+      None => changes.push(change(ChangeKind::Delete, old, vec![])),
+      Some(new) => {
+        let mut fields = vec![];
+        diff_objects("", old, new, &mut fields);
+
+        if !fields.is_empty() {
+          changes.push(change(ChangeKind::Update, new, fields));
+        }
+      }
+    }
+  }
+
+  for new in after.iter().filter(|new| !before.iter().any(|old| identity(collection, old) == identity(collection, new))) {
+    changes.push(change(ChangeKind::Create, new, vec![]));
+  }
+
+  changes
+}
+
+fn diff_objects(prefix: &str, before: &Map<String, Value>, after: &Map<String, Value>, fields: &mut Vec<FieldChange>) {
+  let mut keys = before.keys().chain(after.keys()).collect::<Vec<_>>();
+
+  keys.sort();
+  keys.dedup();
+
+  for key in keys {
+    let path = format!("{}{}", prefix, key);
+    let old = before.get(key).unwrap_or(&Value::Null);
+    let new = after.get(key).unwrap_or(&Value::Null);
+
+    match (old, new) {
+      (Value::Object(old), Value::Object(new)) => diff_objects(&format!("{}.", path), old, new, fields),
+      _ if old != new => fields.push(FieldChange {
+        field: path,
+        before: old.clone(),
+        after: new.clone(),
+      }),
+      _ => {}
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+  use serde_json::{json, Map, Value};
+
+  use super::{SiteSnapshot, REDACTED, SNAPSHOT_VERSION};
+  use crate::{ChangeKind, UnifiedError};
+
+  fn object(value: Value) -> Map<String, Value> {
+    match value {
+      Value::Object(object) => object,
+      _ => unreachable!(),
+    }
+  }
+
+  fn snapshot() -> SiteSnapshot {
+    SiteSnapshot {
+      version: SNAPSHOT_VERSION,
+      site: "default".to_string(),
+      taken_at: Utc::now(),
+      redacted: false,
+      networks: vec![],
+      wireless_networks: vec![],
+      ap_groups: vec![],
+      radius_profiles: vec![],
+      radius_users: vec![],
+      port_profiles: vec![],
+      firewall_rules: vec![],
+      settings: vec![],
+    }
+  }
+
+  #[test]
+  fn redact_nested_secrets() {
+    let mut snapshot = snapshot();
+
+    snapshot.radius_profiles = vec![object(json!({
+      "_id": "1",
+      "name": "Default",
+      "auth_servers": [{ "ip": "10.0.0.1", "x_secret": "hunter2" }],
+      "x_unset": null,
+    }))];
+
+    snapshot.redact();
+
+    let profile = &snapshot.radius_profiles[0];
+
+    assert!(snapshot.redacted);
+    assert_eq!(profile["auth_servers"][0]["x_secret"], REDACTED);
+    assert_eq!(profile["auth_servers"][0]["ip"], "10.0.0.1");
+    assert_eq!(profile["x_unset"], Value::Null);
+  }
+
+  #[test]
+  fn diff_detects_changes() {
+    let mut before = snapshot();
+    let mut after = snapshot();
+
+    before.networks = vec![
+      object(json!({ "_id": "1", "name": "Employees", "dhcpd": { "enabled": true, "start": "10.0.0.1" } })),
+      object(json!({ "_id": "2", "name": "Guests" })),
+    ];
+    after.networks = vec![
+      object(json!({ "_id": "1", "name": "Employees", "dhcpd": { "enabled": false, "start": "10.0.0.1" } })),
+      object(json!({ "_id": "3", "name": "IoT" })),
+    ];
+
+    let changes = before.diff(&after);
+
+    assert_eq!(changes.len(), 3);
+
+    assert_eq!(changes[0].kind, ChangeKind::Update);
+    assert_eq!(changes[0].id, "1");
+    assert_eq!(changes[0].fields.len(), 1);
+    assert_eq!(changes[0].fields[0].field, "dhcpd.enabled");
+    assert_eq!(changes[0].fields[0].before, true);
+    assert_eq!(changes[0].fields[0].after, false);
+
+    assert_eq!(changes[1].kind, ChangeKind::Delete);
+    assert_eq!(changes[1].name.as_deref(), Some("Guests"));
+
+    assert_eq!(changes[2].kind, ChangeKind::Create);
+    assert_eq!(changes[2].name.as_deref(), Some("IoT"));
+  }
+
+  #[test]
+  fn diff_matches_settings_by_key() {
+    let mut before = snapshot();
+    let mut after = snapshot();
+
+    before.settings = vec![object(json!({ "_id": "1", "key": "mgmt", "led_enabled": true }))];
+    after.settings = vec![object(json!({ "_id": "2", "key": "mgmt", "led_enabled": false }))];
+
+    let changes = before.diff(&after);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Update);
+    assert_eq!(changes[0].id, "mgmt");
+    assert!(changes[0].fields.iter().any(|field| field.field == "led_enabled"));
+  }
+
+  #[test]
+  fn from_json_rejects_unknown_versions() {
+    let mut snapshot = snapshot();
+    snapshot.version = SNAPSHOT_VERSION + 1;
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+
+    assert!(matches!(SiteSnapshot::from_json(&json), Err(UnifiedError::UnsupportedVersion(version)) if version == SNAPSHOT_VERSION + 1));
+
+    snapshot.version = SNAPSHOT_VERSION;
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+
+    assert!(SiteSnapshot::from_json(&json).is_ok());
+  }
+}
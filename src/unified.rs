@@ -1,19 +1,95 @@
+use std::{
+  sync::{Arc, OnceLock, PoisonError, RwLock},
+  time::Duration,
+};
+
+use chrono::{DateTime, Utc};
 use cookie::Cookie;
 use serde_json::json;
 
 use crate::{
-  http::{ApiV1NoData, Scheme, UdmProAuthResponse, UnifiData, UnifiResponse},
+  cache::Cache,
+  http::{tls, ApiV1NoData, RetryPolicy, Scheme, UdmProAuthResponse, UnifiData, UnifiResponse},
   UnifiedError,
 };
 
+/// Status code used by UniFi OS to signal that a second factor is required.
+const MFA_REQUIRED_STATUS: u16 = 499;
+
+#[derive(Clone, Default)]
+pub(crate) struct SessionState {
+  pub(crate) token: String,
+  pub(crate) csrf: String,
+  pub(crate) expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub(crate) struct Credentials {
+  username: String,
+  password: String,
+}
+
+/// Type of controller, as detected on the first exchange with it.
+#[derive(Clone, Default)]
+pub(crate) struct Flavour {
+  pub(crate) is_udm_pro: bool,
+  pub(crate) detected: bool,
+  pub(crate) version: Option<String>,
+}
+
 /// Handle to an authenticated connection to a Unifi controller.
+///
+/// The handle is cheap to clone: clones share the same configuration,
+/// session and HTTP client, so they can be handed to other tasks once the
+/// handle is configured and authenticated. Configuring a handle that was
+/// already cloned detaches it from its clones.
+#[derive(Clone)]
 pub struct Unified {
+  pub(crate) inner: Arc<Inner>,
+}
+
+pub(crate) struct Inner {
   pub(crate) scheme: Scheme,
   pub(crate) host: String,
-  pub(crate) token: String,
-  pub(crate) csrf: String,
+  pub(crate) session: RwLock<SessionState>,
+  pub(crate) credentials: Option<Credentials>,
+  pub(crate) api_key: Option<String>,
+  pub(crate) reauthenticate: bool,
   pub(crate) tls_verify: bool,
-  pub(crate) is_udm_pro: bool,
+  pub(crate) ca_certificates: Vec<reqwest::Certificate>,
+  pub(crate) pinned_fingerprint: Option<[u8; 32]>,
+  pub(crate) flavour: RwLock<Flavour>,
+  pub(crate) timeout: Option<Duration>,
+  pub(crate) connect_timeout: Option<Duration>,
+  pub(crate) retry: Option<RetryPolicy>,
+  pub(crate) check_conflicts: bool,
+  pub(crate) cache: Cache,
+  pub(crate) http: OnceLock<reqwest::Client>,
+  pub(crate) custom_http: bool,
+}
+
+impl Clone for Inner {
+  fn clone(&self) -> Inner {
+    Inner {
+      scheme: self.scheme,
+      host: self.host.clone(),
+      session: RwLock::new(self.session.read().unwrap_or_else(PoisonError::into_inner).clone()),
+      credentials: self.credentials.clone(),
+      api_key: self.api_key.clone(),
+      reauthenticate: self.reauthenticate,
+      tls_verify: self.tls_verify,
+      ca_certificates: self.ca_certificates.clone(),
+      pinned_fingerprint: self.pinned_fingerprint,
+      flavour: RwLock::new(self.flavour.read().unwrap_or_else(PoisonError::into_inner).clone()),
+      timeout: self.timeout,
+      connect_timeout: self.connect_timeout,
+      retry: self.retry.clone(),
+      check_conflicts: self.check_conflicts,
+      cache: self.cache.clone(),
+      http: self.http.clone(),
+      custom_http: self.custom_http,
+    }
+  }
 }
 
 impl Unified {
@@ -30,15 +106,51 @@ impl Unified {
   /// ```
   pub fn new(host: &str) -> Unified {
     Unified {
-      scheme: Scheme::Https,
-      host: host.to_string(),
-      token: String::new(),
-      csrf: String::new(),
-      tls_verify: true,
-      is_udm_pro: false,
+      inner: Arc::new(Inner {
+        scheme: Scheme::Https,
+        host: host.to_string(),
+        session: RwLock::new(SessionState::default()),
+        credentials: None,
+        api_key: None,
+        reauthenticate: false,
+        tls_verify: true,
+        ca_certificates: Vec::new(),
+        pinned_fingerprint: None,
+        flavour: RwLock::new(Flavour::default()),
+        timeout: None,
+        connect_timeout: None,
+        retry: None,
+        check_conflicts: false,
+        cache: Cache::default(),
+        http: OnceLock::new(),
+        custom_http: false,
+      }),
     }
   }
 
+  /// Creates a Unified handle authenticating with an API key.
+  ///
+  /// API keys can be created on recent versions of the UniFi Network
+  /// Application. Every request will carry the key in the `X-API-KEY` header,
+  /// so no login is needed. Unless it was set through [`Unified::udm_pro`],
+  /// the type of controller is detected on the first request, so requests are
+  /// routed properly on UniFi OS consoles.
+  ///
+  /// # Arguments
+  ///
+  ///  * `host`    - Hostname and port of the Unifi controller
+  ///  * `api_key` - API key created on the controller
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::with_api_key("unifi.acme.corp", "abcdefgh");
+  /// let networks = unifi.networks("default").await?;
+  /// ```
+  pub fn with_api_key(host: &str, api_key: &str) -> Unified {
+    Unified::new(host).configure(|inner| inner.api_key = Some(api_key.to_string()))
+  }
+
   /// Use HTTP instead of HTTPS for the connection to the controller.
   ///
   /// # Example
@@ -46,33 +158,182 @@ impl Unified {
   /// ```
   /// let unifi = Unified::new("unifi.acme.corp").plaintext();
   /// ```
-  pub fn plaintext(mut self) -> Unified {
-    self.scheme = Scheme::Http;
-    self
+  pub fn plaintext(self) -> Unified {
+    self.configure_client(|inner| inner.scheme = Scheme::Http)
   }
 
   /// Accept self-signed certificates.
   ///
+  /// This is ignored if a certificate is pinned with
+  /// [`pin_certificate()`](Unified::pin_certificate).
+  ///
   /// # Example
   ///
   /// ```
   /// let unifi = Unified::new("unifi.acme.corp").no_tls_verify();
   /// ```
-  pub fn no_tls_verify(mut self) -> Unified {
-    self.tls_verify = false;
-    self
+  pub fn no_tls_verify(self) -> Unified {
+    self.configure_client(|inner| inner.tls_verify = false)
+  }
+
+  /// Trust the certificate authorities from the provided PEM bundle, in
+  /// addition to the system's.
+  ///
+  /// The bundle is ignored if a certificate is pinned with
+  /// [`pin_certificate()`](Unified::pin_certificate), which takes precedence.
+  ///
+  /// # Arguments
+  ///
+  ///  * `pem` - PEM-encoded certificates of the authorities to trust
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").ca_certificates(&std::fs::read("acme-ca.pem")?)?;
+  /// ```
+  pub fn ca_certificates(self, pem: &[u8]) -> Result<Unified, UnifiedError> {
+    let certificates = reqwest::Certificate::from_pem_bundle(pem)?;
+
+    Ok(self.configure_client(|inner| inner.ca_certificates.extend(certificates)))
+  }
+
+  /// Only accept the controller certificate matching the provided SHA-256
+  /// fingerprint.
+  ///
+  /// The certificate is accepted whoever issued it, which allows for
+  /// self-signed certificates to be verified. Requests to a controller
+  /// presenting another certificate fail with
+  /// [`UnifiedError::CertificateMismatch`].
+  ///
+  /// Pinning takes precedence over any other TLS setting on the handle:
+  /// [`ca_certificates()`](Unified::ca_certificates) and
+  /// [`no_tls_verify()`](Unified::no_tls_verify) are ignored.
+  ///
+  /// # Arguments
+  ///
+  ///  * `fingerprint` - Hex-encoded fingerprint, optionally separated by colons
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").pin_certificate("AB:CD:EF:...:01:23")?;
+  /// ```
+  pub fn pin_certificate(self, fingerprint: &str) -> Result<Unified, UnifiedError> {
+    let fingerprint = tls::parse_fingerprint(fingerprint)?;
+
+    Ok(self.configure_client(|inner| inner.pinned_fingerprint = Some(fingerprint)))
   }
 
   /// The controller runs on a Unifi Dream Machine Pro.
   ///
+  /// This skips the detection of the type of controller that is otherwise
+  /// performed by [`Unified::auth`].
+  ///
   /// # Example
   ///
   /// ```
   /// let unifi = Unified::new("unifi.acme.corp").udm_pro();
   /// ```
-  pub fn udm_pro(mut self) -> Unified {
-    self.is_udm_pro = true;
-    self
+  pub fn udm_pro(self) -> Unified {
+    self.configure(|inner| {
+      let flavour = inner.flavour.get_mut().unwrap_or_else(PoisonError::into_inner);
+
+      flavour.is_udm_pro = true;
+      flavour.detected = true;
+    })
+  }
+
+  /// Set the maximum duration of a request to the controller.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").timeout(Duration::from_secs(10));
+  /// ```
+  pub fn timeout(self, timeout: Duration) -> Unified {
+    self.configure_client(|inner| inner.timeout = Some(timeout))
+  }
+
+  /// Set the maximum duration to establish a connection to the controller.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").connect_timeout(Duration::from_secs(3));
+  /// ```
+  pub fn connect_timeout(self, timeout: Duration) -> Unified {
+    self.configure_client(|inner| inner.connect_timeout = Some(timeout))
+  }
+
+  /// Retry requests failing because of transient errors.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").retry(RetryPolicy::new(3));
+  /// ```
+  pub fn retry(self, policy: RetryPolicy) -> Unified {
+    self.configure(|inner| inner.retry = Some(policy))
+  }
+
+  /// Check that objects were not modified on the controller before updating
+  /// them.
+  ///
+  /// When enabled, every `update()` first reads the object again and compares
+  /// it with the version that was loaded. If someone else changed it in the
+  /// meantime, the update is aborted with [`UnifiedError::Conflict`], or with
+  /// [`UnifiedError::NoSuchObject`] if it was deleted. Objects that were not
+  /// read from the controller (e.g. deserialized ones) cannot be checked, and
+  /// updating them fails with [`UnifiedError::NotLoaded`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").check_conflicts();
+  /// ```
+  pub fn check_conflicts(self) -> Unified {
+    self.configure(|inner| inner.check_conflicts = true)
+  }
+
+  /// Use a pre-configured HTTP client for all requests to the controller.
+  ///
+  /// The provided client is used as-is, so the TLS and timeout settings set
+  /// on this handle will not be applied to it.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let client = reqwest::Client::builder().user_agent("acme-inventory/1.0").build()?;
+  /// let unifi = Unified::new("unifi.acme.corp").http_client(client);
+  /// ```
+  pub fn http_client(self, client: reqwest::Client) -> Unified {
+    self.configure(|inner| {
+      inner.http = OnceLock::from(client);
+      inner.custom_http = true;
+    })
+  }
+
+  /// Transparently log in again when the controller expires the session.
+  ///
+  /// When enabled, the credentials given to [`Unified::auth`] are kept in
+  /// memory, and a request rejected because of an expired session is retried
+  /// once after a new login.
+  ///
+  /// This must be called before authenticating: credentials are only kept by
+  /// a login performed after re-authentication was enabled, so enabling it on
+  /// a handle that is already authenticated has no effect.
+  ///
+  /// Accounts protected by multi-factor authentication cannot log in again
+  /// without a new token, so the expired request fails with
+  /// [`UnifiedError::MfaRequired`] instead.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").reauthenticate().auth("joe.shmoe", "mypassword").await?;
+  /// ```
+  pub fn reauthenticate(self) -> Unified {
+    self.configure(|inner| inner.reauthenticate = true)
   }
 
   /// Use a previously acquired token.
@@ -82,13 +343,16 @@ impl Unified {
   /// ```
   /// let unifi = Unified::new("unifi.acme.corp").set_token("unifises=abcdefgh");
   /// ```
-  pub fn set_token(mut self, token: &str) -> Unified {
-    self.token = token.to_string();
+  pub fn set_token(self, token: &str) -> Unified {
+    self.inner.session.write().unwrap_or_else(PoisonError::into_inner).token = token.to_string();
     self
   }
 
   /// Authenticate into a Unifi controller with the provided username and password.
   ///
+  /// Unless it was already known, the type of controller is detected before
+  /// logging in (see [`Unified::detect`]).
+  ///
   /// # Arguments
   ///
   ///  * `username` - Username of the account
@@ -99,44 +363,204 @@ impl Unified {
   /// ```
   /// let unifi = Unified::new("unifi.acme.corp").auth("joe.shmoe", "mypassword").await?;
   /// ```
-  pub async fn auth(mut self, username: &str, password: &str) -> Result<Unified, UnifiedError> {
-    let credentials = json!({
+  pub async fn auth(self, username: &str, password: &str) -> Result<Unified, UnifiedError> {
+    self.authenticate(username, password, None).await
+  }
+
+  /// Authenticate into a Unifi controller with the provided username,
+  /// password and second authentication factor.
+  ///
+  /// # Arguments
+  ///
+  ///  * `username` - Username of the account
+  ///  * `password` - Password of the account
+  ///  * `token`    - Second factor (e.g. TOTP code) for the account
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let unifi = Unified::new("unifi.acme.corp").auth_mfa("joe.shmoe", "mypassword", "123456").await?;
+  /// ```
+  pub async fn auth_mfa(self, username: &str, password: &str, token: &str) -> Result<Unified, UnifiedError> {
+    self.authenticate(username, password, Some(token)).await
+  }
+
+  pub(crate) async fn authenticate(self, username: &str, password: &str, token: Option<&str>) -> Result<Unified, UnifiedError> {
+    if !self.detected() {
+      self.probe().await?;
+    }
+
+    self.login(username, password, token).await?;
+
+    Ok(self.remember_credentials(username, password))
+  }
+
+  pub(crate) fn remember_credentials(self, username: &str, password: &str) -> Unified {
+    match self.inner.reauthenticate {
+      true => self.configure(|inner| {
+        inner.credentials = Some(Credentials {
+          username: username.to_string(),
+          password: password.to_string(),
+        })
+      }),
+
+      false => self,
+    }
+  }
+
+  pub(crate) async fn login(&self, username: &str, password: &str, token: Option<&str>) -> Result<(), UnifiedError> {
+    let mut credentials = json!({
       "username": username.to_string(),
       "password": password.to_string(),
       "remember": true,
     });
 
-    let url = match self.is_udm_pro {
-      true => format!("{}://{}/api/auth/login", self.scheme.as_str(), self.host),
-      false => format!("{}://{}/api/login", self.scheme.as_str(), self.host),
+    if let Some(token) = token {
+      match self.is_udm_pro() {
+        true => credentials["token"] = json!(token),
+        false => credentials["ubic_2fa_token"] = json!(token),
+      }
+    }
+
+    let path = match self.is_udm_pro() {
+      true => "/api/auth/login",
+      false => "/api/login",
     };
 
-    let client = reqwest::ClientBuilder::new().danger_accept_invalid_certs(!self.tls_verify).build()?;
-    let response = client.post(&url).json(&credentials).send().await?;
+    let response = self
+      .http()?
+      .post(format!("{}://{}{}", self.inner.scheme.as_str(), self.inner.host, path))
+      .json(&credentials)
+      .send()
+      .await?;
+    let status = response.status();
+
+    if status.as_u16() == MFA_REQUIRED_STATUS {
+      return Err(UnifiedError::MfaRequired);
+    }
 
     let cookies = response
       .headers()
       .get_all("set-cookie")
       .into_iter()
-      .map(|cookie| Cookie::parse(cookie.to_str().unwrap_or_default()).ok())
-      .flatten()
-      .map(|cookie| format!("{}={}", cookie.name(), cookie.value()))
-      .collect::<Vec<String>>();
+      .filter_map(|cookie| Cookie::parse(cookie.to_str().unwrap_or_default().to_string()).ok())
+      .collect::<Vec<Cookie>>();
+
+    let mut session = SessionState {
+      expires_at: cookies.iter().filter_map(cookie_expiration).min(),
+      ..SessionState::default()
+    };
 
-    match self.is_udm_pro {
+    match self.is_udm_pro() {
       true => {
         if let Some(csrf) = response.headers().get("x-csrf-token") {
-          self.csrf = csrf.to_str().unwrap_or_default().to_string();
+          session.csrf = csrf.to_str().unwrap_or_default().to_string();
         }
 
-        response.deserialize::<UdmProAuthResponse>().await?.catch()?;
+        response.deserialize::<UdmProAuthResponse>(path).await?.catch().map_err(|err| err.in_request(status, path))?;
+      }
+
+      false => match response.deserialize::<ApiV1NoData>(path).await.and_then(UnifiData::catch) {
+        Err(err) if err.api_error().map(|err| err.key == "api.err.Ubic2faTokenRequired").unwrap_or_default() => return Err(UnifiedError::MfaRequired),
+        result => result.map_err(|err| err.in_request(status, path))?,
+      },
+    }
+
+    session.token = cookies.iter().map(|cookie| format!("{}={}", cookie.name(), cookie.value())).collect::<Vec<String>>().join("; ");
+
+    *self.inner.session.write().unwrap_or_else(PoisonError::into_inner) = session;
+
+    Ok(())
+  }
+
+  /// Log in again with the stored credentials, if transparent
+  /// re-authentication was enabled.
+  ///
+  /// Returns whether a new session was acquired.
+  pub(crate) async fn relogin(&self) -> Result<bool, UnifiedError> {
+    match self.inner.credentials {
+      Some(ref credentials) if self.inner.reauthenticate => {
+        self.login(&credentials.username, &credentials.password, None).await?;
+
+        Ok(true)
+      }
+
+      _ => Ok(false),
+    }
+  }
+
+  /// Change the configuration of the handle.
+  ///
+  /// The configuration is shared by all clones of a handle, so a handle that
+  /// was already cloned is first detached from its clones.
+  pub(crate) fn configure<F>(mut self, configure: F) -> Unified
+  where
+    F: FnOnce(&mut Inner),
+  {
+    configure(Arc::make_mut(&mut self.inner));
+    self
+  }
+
+  /// Change a setting of the HTTP client, which is built again on next use
+  /// unless it was provided through [`Unified::http_client`].
+  fn configure_client<F>(self, configure: F) -> Unified
+  where
+    F: FnOnce(&mut Inner),
+  {
+    self.configure(|inner| {
+      configure(inner);
+
+      if !inner.custom_http {
+        inner.http = OnceLock::new();
       }
+    })
+  }
+
+  pub(crate) fn session_state(&self) -> SessionState {
+    self.inner.session.read().unwrap_or_else(PoisonError::into_inner).clone()
+  }
+
+  /// Get the HTTP client shared by all requests made through this handle,
+  /// building it on first use.
+  pub(crate) fn http(&self) -> Result<&reqwest::Client, UnifiedError> {
+    if let Some(client) = self.inner.http.get() {
+      return Ok(client);
+    }
+
+    let mut builder = match self.inner.pinned_fingerprint {
+      Some(fingerprint) => reqwest::ClientBuilder::new().use_preconfigured_tls(tls::pinned_config(fingerprint)),
+      None => reqwest::ClientBuilder::new().danger_accept_invalid_certs(!self.inner.tls_verify),
+    };
 
-      false => response.deserialize::<ApiV1NoData>().await?.catch()?,
+    for certificate in &self.inner.ca_certificates {
+      builder = builder.add_root_certificate(certificate.clone());
     }
 
-    self.token = cookies.join("; ");
+    builder = builder
+      .https_only(matches!(self.inner.scheme, Scheme::Https))
+      .tcp_keepalive(Duration::from_secs(60))
+      .pool_idle_timeout(Duration::from_secs(90));
+
+    if let Some(timeout) = self.inner.timeout {
+      builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = self.inner.connect_timeout {
+      builder = builder.connect_timeout(timeout);
+    }
 
-    Ok(self)
+    let client = builder.build()?;
+
+    Ok(self.inner.http.get_or_init(|| client))
   }
 }
+
+fn cookie_expiration(cookie: &Cookie) -> Option<DateTime<Utc>> {
+  if let Some(max_age) = cookie.max_age() {
+    return Some(Utc::now() + chrono::Duration::seconds(max_age.whole_seconds()));
+  }
+
+  cookie
+    .expires()
+    .and_then(|expiration| expiration.datetime())
+    .and_then(|expiration| DateTime::from_timestamp(expiration.unix_timestamp(), 0))
+}
@@ -1,3 +1,27 @@
 pub(crate) const fn is_true() -> bool {
   true
 }
+
+/// Serialize MAC addresses as the lowercase strings used by the controller.
+pub(crate) mod mac_address {
+  use std::str::FromStr;
+
+  use macaddr::MacAddr;
+  use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+  pub(crate) fn serialize<S>(mac: &MacAddr, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_str(&mac.to_string().to_lowercase())
+  }
+
+  pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<MacAddr, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let mac = String::deserialize(deserializer)?;
+
+    MacAddr::from_str(&mac).map_err(D::Error::custom)
+  }
+}
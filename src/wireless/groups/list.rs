@@ -1,6 +1,4 @@
-use reqwest::Method;
-
-use crate::{http::ApiV2, wireless::groups::types::*, Unified, UnifiedError};
+use crate::{cache::Collection, http::ApiV2, wireless::groups::types::*, Unified, UnifiedError};
 
 impl Unified {
   /// List pre-existing Access Point groups.
@@ -14,13 +12,14 @@ impl Unified {
   /// ```
   /// let groups = unifi.ap_groups("default").await?;
   /// ```
-  pub async fn ap_groups(&self, site: &str) -> Result<Vec<ApGroup<'_>>, UnifiedError> {
-    let response = self.request::<ApiV2<Vec<RemoteApGroup>>>(Method::GET, &format!("/v2/api/site/{}/apgroups", site)).query().await?;
+  pub async fn ap_groups(&self, site: &str) -> Result<Vec<ApGroup>, UnifiedError> {
+    let response = self
+      .cached::<ApiV2<Vec<RemoteApGroup>>, _>(Some(site), Collection::ApGroups, &format!("/v2/api/site/{}/apgroups", site))
+      .await?;
 
     let groups = response
       .into_iter()
       .map(|group| ApGroup {
-        unified: self,
         site: site.to_string(),
 
         id: group.id,
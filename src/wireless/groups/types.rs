@@ -1,8 +1,6 @@
-use crate::Unified;
-
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct RemoteApGroup {
   #[serde(rename = "_id")]
   pub id: String,
@@ -10,11 +8,8 @@ pub struct RemoteApGroup {
 }
 
 /// Group of related wireless Access Points
-#[derive(Clone, Derivative)]
-#[derivative(Debug)]
-pub struct ApGroup<'g> {
-  #[derivative(Debug = "ignore")]
-  pub(crate) unified: &'g Unified,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApGroup {
   pub(crate) site: String,
 
   /// Internal ID
@@ -1,13 +1,14 @@
 use reqwest::Method;
-use serde_json::json;
+use serde_json::{json, Map};
 
 use crate::{
+  cache::Collection,
   http::ApiV1NoData,
   wireless::networks::{builder::WirelessNetworkBuilder, types::*},
   Unified, UnifiedError,
 };
 
-impl<'wn> WirelessNetwork<'wn> {
+impl WirelessNetwork {
   /// Create a builder for a wireless network.
   ///
   /// # Arguments
@@ -25,10 +26,10 @@ impl<'wn> WirelessNetwork<'wn> {
   ///   .wpa(WirelessNetworkWpaMode::Wpa2)
   ///   .build()?;
   /// ```
-  pub fn builder(unified: &'wn Unified, site: &str, name: &str) -> WirelessNetworkBuilder<'wn> {
+  pub fn builder(unified: &Unified, site: &str, name: &str) -> WirelessNetworkBuilder {
     WirelessNetworkBuilder {
       network: WirelessNetwork {
-        unified,
+        unified: Some(unified.clone()),
         site: site.to_string(),
         id: String::new(),
         name: name.to_string(),
@@ -42,41 +43,39 @@ impl<'wn> WirelessNetwork<'wn> {
         vlan: None,
         radius_profile: None,
         advertised: true,
+
+        raw: Map::new(),
+        snapshot: Map::new(),
       },
     }
   }
 
   /// Create the wireless network.
   ///
+  /// The wireless network is returned as created by the controller, with its
+  /// ID and server-side defaults populated.
+  ///
   /// # Example
   ///
   /// ```
-  /// WirelessNetwork::builder(&unifi, "default", "ACME Corp")
+  /// let network = WirelessNetwork::builder(&unifi, "default", "ACME Corp")
   ///   .ap_groups(vec!["6105d873b49ca605191f4331"])
   ///   .network("6105dd31b49ca605191f4373")
   ///   .security(WirelessNetworkSecurity::WpaPsk)
   ///   .wpa(WirelessNetworkWpaMode::Wpa2)
-  ///   .build()?;
+  ///   .build()?
   ///   .create()
   ///   .await?;
   /// ```
-  pub async fn create(self) -> Result<(), UnifiedError> {
-    let body: RemoteWirelessNetwork = self.clone().into();
-
-    println!("{}", serde_json::to_string(&body).unwrap());
-
-    self
-      .unified
-      .request::<ApiV1NoData>(Method::POST, &format!("/api/s/{}/rest/wlanconf", self.site))
-      .map(|r| r.json(&body))
-      .query()
-      .await?;
-
-    Ok(())
+  pub async fn create(self) -> Result<WirelessNetwork, UnifiedError> {
+    self.handle()?.create_resource(&self).await
   }
 
   /// Update the wireless network.
   ///
+  /// Only its [`changes()`](Self::changes) are sent to the controller (see
+  /// [`Unified::update_resource`]).
+  ///
   /// # Example
   ///
   /// ```
@@ -86,16 +85,7 @@ impl<'wn> WirelessNetwork<'wn> {
   /// }
   /// ```
   pub async fn update(self) -> Result<(), UnifiedError> {
-    let body: RemoteWirelessNetwork = self.clone().into();
-
-    self
-      .unified
-      .request::<ApiV1NoData>(Method::PUT, &format!("/api/s/{}/rest/wlanconf/{}", self.site, self.id))
-      .map(|r| r.json(&body))
-      .query()
-      .await?;
-
-    Ok(())
+    self.handle()?.update_resource(&self).await
   }
 
   /// Delete the wireless network.
@@ -108,13 +98,7 @@ impl<'wn> WirelessNetwork<'wn> {
   /// }
   /// ```
   pub async fn delete(self) -> Result<(), UnifiedError> {
-    self
-      .unified
-      .request::<ApiV1NoData>(Method::DELETE, &format!("/api/s/{}/rest/wlanconf/{}", self.site, self.id))
-      .query()
-      .await?;
-
-    Ok(())
+    self.handle()?.delete_resource::<WirelessNetwork>(&self.site, &self.id).await
   }
 
   /// Enable the wireless network.
@@ -144,13 +128,16 @@ impl<'wn> WirelessNetwork<'wn> {
   }
 
   async fn set_state(&self, state: bool) -> Result<(), UnifiedError> {
-    self
-      .unified
+    let unified = self.handle()?;
+
+    unified
       .request::<ApiV1NoData>(Method::PUT, &format!("/api/s/{}/rest/wlanconf/{}", self.site, self.id))
       .map(|r| r.json(&json!({ "enabled": state })))
       .query()
       .await?;
 
+    unified.invalidate(&self.site, Collection::WirelessNetworks);
+
     Ok(())
   }
 }
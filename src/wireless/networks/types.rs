@@ -1,8 +1,16 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-use crate::Unified;
+use crate::{
+  cache::Collection,
+  changes::{self, FieldChange},
+  http::Raw,
+  RestResource, Unified, UnifiedError,
+};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub(super) struct RemoteWirelessNetwork {
   #[serde(rename = "_id", skip_serializing)]
   pub id: String,
@@ -29,7 +37,7 @@ pub(super) struct RemoteWirelessNetwork {
   pub radius_profile: Option<String>,
 }
 
-impl From<WirelessNetwork<'_>> for RemoteWirelessNetwork {
+impl From<WirelessNetwork> for RemoteWirelessNetwork {
   fn from(network: WirelessNetwork) -> RemoteWirelessNetwork {
     RemoteWirelessNetwork {
       id: network.id,
@@ -60,9 +68,11 @@ pub enum WirelessNetworkRef<'r> {
 /// A configured wireless network.
 #[derive(Clone, Derivative)]
 #[derivative(Debug)]
-pub struct WirelessNetwork<'wn> {
+#[derive(Serialize, Deserialize)]
+pub struct WirelessNetwork {
   #[derivative(Debug = "ignore")]
-  pub(crate) unified: &'wn Unified,
+  #[serde(skip)]
+  pub(crate) unified: Option<Unified>,
   pub(crate) site: String,
 
   /// Internal ID
@@ -89,19 +99,57 @@ pub struct WirelessNetwork<'wn> {
   pub vlan: Option<u16>,
   /// RADIUS profile to use in case of 802.1x
   pub radius_profile: Option<String>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip)]
+  pub(crate) raw: Map<String, Value>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip)]
+  pub(crate) snapshot: Map<String, Value>,
+}
+
+impl WirelessNetwork {
+  /// Bind a deserialized wireless network to a handle (see [serialization](crate#serialization)).
+  pub fn attach(&mut self, unified: &Unified) {
+    self.unified = Some(unified.clone());
+  }
+
+  /// Handle the wireless network is bound to.
+  pub(crate) fn handle(&self) -> Result<&Unified, UnifiedError> {
+    self.unified.as_ref().ok_or(UnifiedError::NotAttached)
+  }
+
+  /// Raw JSON object the wireless network was read from.
+  ///
+  /// This includes the attributes that are not modelled by this crate, which
+  /// are left untouched when the wireless network is updated.
+  pub fn raw(&self) -> &Map<String, Value> {
+    &self.raw
+  }
+
+  /// Attributes changed since the wireless network was read from the controller.
+  ///
+  /// Those are the only attributes sent to the controller by
+  /// [`update()`](WirelessNetwork::update).
+  pub fn changes(&self) -> Vec<FieldChange> {
+    changes::diff(&self.snapshot, &self.to_json())
+  }
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WirelessBand {
   Invalid,
+  #[serde(rename = "2g")]
   Band2G,
+  #[serde(rename = "5g")]
   Band5G,
   Both,
 }
 
-impl ToString for WirelessBand {
-  fn to_string(&self) -> String {
+impl fmt::Display for WirelessBand {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let value = match self {
       Self::Invalid => "invalid",
       Self::Band2G => "2g",
@@ -109,7 +157,7 @@ impl ToString for WirelessBand {
       Self::Both => "both",
     };
 
-    value.to_string()
+    write!(f, "{}", value)
   }
 }
 
@@ -128,17 +176,22 @@ where
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum WirelessNetworkSecurity {
+  #[serde(rename = "invalid")]
   Invalid,
+  #[serde(rename = "open")]
   Open,
+  #[serde(rename = "wep")]
   Wep,
+  #[serde(rename = "wpapsk")]
   WpaPsk,
+  #[serde(rename = "wpaeap")]
   WpaEap,
 }
 
-impl ToString for WirelessNetworkSecurity {
-  fn to_string(&self) -> String {
+impl fmt::Display for WirelessNetworkSecurity {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let value = match self {
       Self::Invalid => "invalid",
       Self::Open => "open",
@@ -147,7 +200,7 @@ impl ToString for WirelessNetworkSecurity {
       Self::WpaEap => "wpaeap",
     };
 
-    value.to_string()
+    write!(f, "{}", value)
   }
 }
 
@@ -167,7 +220,7 @@ where
 }
 
 /// The WPA security settings for a wireless network.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WirelessNetworkWpa {
   /// Supported WPA versions
   pub mode: WirelessNetworkWpaMode,
@@ -176,7 +229,8 @@ pub struct WirelessNetworkWpa {
 }
 
 /// WPA versions.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WirelessNetworkWpaMode {
   /// This value should not be used.
   Invalid,
@@ -188,8 +242,8 @@ pub enum WirelessNetworkWpaMode {
   Both,
 }
 
-impl ToString for WirelessNetworkWpaMode {
-  fn to_string(&self) -> String {
+impl fmt::Display for WirelessNetworkWpaMode {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let value = match self {
       Self::Invalid => "invalid",
       Self::Wpa1 => "wpa1",
@@ -197,7 +251,7 @@ impl ToString for WirelessNetworkWpaMode {
       Self::Both => "both",
     };
 
-    value.to_string()
+    write!(f, "{}", value)
   }
 }
 
@@ -214,3 +268,30 @@ where
     }
   }
 }
+
+impl RestResource for WirelessNetwork {
+  const COLLECTION: &'static str = "wlanconf";
+  const CACHE: Option<Collection> = Some(Collection::WirelessNetworks);
+
+  fn from_json(unified: &Unified, site: &str, object: Map<String, Value>) -> Result<WirelessNetwork, UnifiedError> {
+    let remote = Raw::deserialize(Value::Object(object)).map_err(UnifiedError::InvalidResponse)?;
+
+    Ok(WirelessNetwork::from_remote(unified, site, remote))
+  }
+
+  fn to_json(&self) -> Map<String, Value> {
+    changes::snapshot(&RemoteWirelessNetwork::from(self.clone()))
+  }
+
+  fn site(&self) -> &str {
+    &self.site
+  }
+
+  fn id(&self) -> &str {
+    &self.id
+  }
+
+  fn loaded(&self) -> Option<&Map<String, Value>> {
+    Some(&self.snapshot)
+  }
+}